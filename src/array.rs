@@ -1,21 +1,128 @@
+use crate::prelude::{anyhow, ErrorKind, Result};
+use crate::scalar;
+
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+
 /// A single element of an array.
 ///
 /// In a more classical algorithmic sense, a potential leaf node in a tree.
 #[derive(Debug, PartialEq, Clone)]
 pub enum Element {
     Array(Array),
-    Int(i64), // TODO: f64, bigint, etc
+    Int(i64),
+    BigInt(BigInt),
+    Rational(BigRational),
+    Float(f64),
+    Complex(Complex64),
     String(String),
+    Atom(Atom),
     None,
 }
 
+/// A symbol like `∆name`.
+///
+/// Atoms are interned, so two atoms with the same name are the same atom and comparing them is cheap.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Atom(usize);
+
+// Every atom name seen so far, indexed by atom, along with the index of each name so it can be found without a scan.
+#[derive(Default)]
+struct Atoms {
+    names: Vec<&'static str>,
+    indices: HashMap<&'static str, usize>,
+}
+
+static ATOMS: LazyLock<Mutex<Atoms>> = LazyLock::new(Mutex::default);
+
+impl Atom {
+    /// Get the atom with a name, without the ∆.
+    pub fn new(name: &str) -> Atom {
+        let mut atoms = ATOMS.lock().unwrap();
+        if let Some(&index) = atoms.indices.get(name) {
+            return Atom(index);
+        }
+
+        // names live as long as the program does
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        let index = atoms.names.len();
+        atoms.names.push(name);
+        atoms.indices.insert(name, index);
+        Atom(index)
+    }
+
+    /// Get the name of the atom, without the ∆.
+    pub fn name(&self) -> &'static str {
+        ATOMS.lock().unwrap().names[self.0]
+    }
+}
+
+impl std::fmt::Display for Atom {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "∆{}", self.name())
+    }
+}
+
 impl Element {
+    /// Check if the element is a number.
+    pub fn is_number(&self) -> bool {
+        matches!(
+            self,
+            Element::Int(_)
+                | Element::BigInt(_)
+                | Element::Rational(_)
+                | Element::Float(_)
+                | Element::Complex(_)
+        )
+    }
+
+    /// Get the value of a number that's a whole number fitting in an i64, whatever kind of number it is.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Element::Int(i) => Some(*i),
+            Element::BigInt(i) => i.to_i64(),
+            Element::Rational(r) if r.is_integer() => r.to_integer().to_i64(),
+            Element::Float(f)
+                if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f < i64::MAX as f64 =>
+            {
+                Some(*f as i64)
+            }
+            Element::Complex(c) if c.im == 0.0 => Element::Float(c.re).as_integer(),
+            _ => None,
+        }
+    }
+
     pub fn as_array(&self) -> Option<&Array> {
         match self {
             Element::Array(array) => Some(array),
             _ => None,
         }
     }
+
+    /// Compare two elements in the total array order. Numbers come before strings, which come before atoms, and
+    /// arrays are compared by their major cells, with a non-array element acting as a scalar.
+    pub fn total_cmp(&self, other: &Element) -> Ordering {
+        order(self, other, &leaf_order)
+    }
+
+    /// Get the element used to fill out arrays made from this one: zero for numbers, an empty string for strings, and
+    /// an array of prototypes for arrays.
+    pub fn prototype(&self) -> Element {
+        match self {
+            Element::Array(array) => Element::Array(array.for_each_monad(Element::prototype)),
+            Element::String(_) => Element::String(String::new()),
+            Element::Atom(_) | Element::None => Element::None,
+            _ => Element::Int(0),
+        }
+    }
 }
 
 /// An array.
@@ -28,21 +135,89 @@ pub struct Array {
     data: Vec<Element>,
 }
 
+impl Default for Array {
+    fn default() -> Self {
+        Array::new()
+    }
+}
+
 impl Array {
+    /// The most elements an array can have. At tens of bytes an element, anything bigger would take more memory than
+    /// most machines have, so making it is an error rather than an allocation failure that aborts.
+    pub const MAX_SIZE: usize = 1 << 26;
+
+    /// Count the elements of an array of a shape, which can't be more than [`Array::MAX_SIZE`].
+    pub fn size(shape: &[usize]) -> Result<usize> {
+        shape
+            .iter()
+            .try_fold(1usize, |size, &length| size.checked_mul(length))
+            .filter(|&size| size <= Array::MAX_SIZE)
+            .ok_or_else(|| {
+                anyhow!(ErrorKind::DomainError {
+                    why: format!("an array of shape {:?} would have too many elements", shape),
+                })
+            })
+    }
+
+    /// Create an empty vector.
     pub fn new() -> Array {
         Array {
-            shape: Vec::new(),
+            shape: vec![0],
             data: Vec::new(),
         }
     }
 
+    /// Create an array from its shape and row-major data.
+    pub fn from_parts(shape: Vec<usize>, data: Vec<Element>) -> Array {
+        assert_eq!(shape.iter().product::<usize>(), data.len());
+        Array { shape, data }
+    }
+
+    /// Create a vector.
+    pub fn vector(data: Vec<Element>) -> Array {
+        Array {
+            shape: vec![data.len()],
+            data,
+        }
+    }
+
+    /// Create a scalar, which has no axes.
     pub fn from_element(element: Element) -> Array {
         Array {
-            shape: vec![1],
+            shape: Vec::new(),
             data: vec![element],
         }
     }
 
+    /// Create a vector of the integers counting up from origin, length of them.
+    pub fn range(length: usize, origin: i64) -> Array {
+        Array::vector(
+            (0..length as i64)
+                .map(|i| Element::Int(i + origin))
+                .collect(),
+        )
+    }
+
+    /// Create an array of the given shape where each element is a vector of its own indices, counting from origin.
+    pub fn indices(shape: &[usize], origin: i64) -> Array {
+        let length = shape.iter().product::<usize>();
+        let data = (0..length)
+            .map(|offset| {
+                Element::Array(Array::vector(
+                    unravel(offset, shape)
+                        .into_iter()
+                        .map(|i| Element::Int(i as i64 + origin))
+                        .collect(),
+                ))
+            })
+            .collect();
+
+        Array {
+            shape: shape.to_vec(),
+            data,
+        }
+    }
+
     pub fn relegate(&self) -> Option<Element> {
         if self.is_scalar() {
             Some(self.data[0].clone())
@@ -55,93 +230,1167 @@ impl Array {
         &self.shape
     }
 
+    /// Get the elements of the array in row-major order.
+    pub fn data(&self) -> &[Element] {
+        &self.data
+    }
+
+    /// Check if the array is a scalar, with no axes. A vector of one element isn't a scalar.
     pub fn is_scalar(&self) -> bool {
-        self.shape.len() == 1 && self.shape[0] == 1
+        self.shape.is_empty()
+    }
+
+    /// Check if the array has exactly one element, whatever its shape.
+    pub fn is_singleton(&self) -> bool {
+        self.data.len() == 1
     }
 
     pub fn for_each_monad<F>(&self, f: F) -> Array
     where
         F: Fn(&Element) -> Element,
     {
-        let data = self.data.iter().map(f).collect();
+        self.try_for_each_monad(|element| Ok(f(element))).unwrap()
+    }
 
-        Array {
+    /// Like for_each_monad, but stops at the first error.
+    pub fn try_for_each_monad<F>(&self, f: F) -> Result<Array>
+    where
+        F: Fn(&Element) -> Result<Element>,
+    {
+        let data = self.data.iter().map(f).collect::<Result<_>>()?;
+
+        Ok(Array {
             shape: self.shape.clone(),
             data,
-        }
+        })
     }
 
     /// Do ⍺f⍵
     ///
     /// Only under the following circumstances:
     ///
-    /// (⍴⍺ = ⍴⍵) ∨ (⍴⍺ = 1) ∨ (⍴⍵ = 1)
-    pub fn for_each_dyad<F>(&self, f: F, array: &Array) -> Array
+    /// (⍴⍺ = ⍴⍵) ∨ (×/⍴⍺ = 1) ∨ (×/⍴⍵ = 1) ∨ (⍴⍺ is a prefix of ⍴⍵) ∨ (⍴⍵ is a prefix of ⍴⍺)
+    ///
+    /// Scalars and singletons are paired with every element of the other array, and otherwise each element of the array with fewer
+    /// axes is paired with every element of the corresponding cell of the other, so a vector of length 3 agrees with a
+    /// 3×5 matrix along its leading axis.
+    pub fn for_each_dyad<F>(&self, f: F, array: &Array) -> Result<Array>
     where
         F: Fn(&Element, &Element) -> Element,
     {
-        assert!(self.shape == array.shape || self.is_scalar() || array.is_scalar());
+        self.try_for_each_dyad(|my, their| Ok(f(my, their)), array)
+    }
 
-        if self.shape == array.shape {
-            let data = self
-                .data
+    /// Like for_each_dyad, but stops at the first error.
+    pub fn try_for_each_dyad<F>(&self, f: F, array: &Array) -> Result<Array>
+    where
+        F: Fn(&Element, &Element) -> Result<Element>,
+    {
+        // scalars have no axes to agree on, and singletons are extended like scalars
+        let frame = |array: &Array| {
+            if array.is_singleton() {
+                Vec::new()
+            } else {
+                array.shape.clone()
+            }
+        };
+        let (mine, theirs) = (frame(self), frame(array));
+
+        // a scalar and a singleton agree, and the result has the singleton's shape
+        let shape = if mine == theirs {
+            if self.shape.len() > array.shape.len() {
+                self.shape.clone()
+            } else {
+                array.shape.clone()
+            }
+        } else if theirs.starts_with(&mine) {
+            array.shape.clone()
+        } else if mine.starts_with(&theirs) {
+            self.shape.clone()
+        } else {
+            return Err(anyhow!(ErrorKind::LengthError {
+                why: format!("shapes {:?} and {:?} don't match", self.shape, array.shape),
+            }));
+        };
+
+        // how many elements of the result each element of either argument is used for
+        let size = shape.iter().product::<usize>();
+        let my_cell = size.checked_div(self.data.len()).unwrap_or(0);
+        let their_cell = size.checked_div(array.data.len()).unwrap_or(0);
+
+        let data = (0..size)
+            .map(|i| f(&self.data[i / my_cell], &array.data[i / their_cell]))
+            .collect::<Result<_>>()?;
+
+        Ok(Array { shape, data })
+    }
+
+    /// The ⌿ operator, reducing along the leading axis.
+    pub fn fold<F>(&self, identity: Option<&Element>, f: F) -> Result<Array>
+    where
+        F: FnMut(&Element, &Element) -> Result<Element>,
+    {
+        self.fold_axis(0, identity, f)
+    }
+
+    /// Reduce along an axis, giving an array with one less axis.
+    ///
+    /// Folds go from right to left, so f/1 2 3 is 1 f (2 f 3). Reducing an axis of length 0 gives the identity of f,
+    /// if it has one.
+    pub fn fold_axis<F>(&self, axis: usize, identity: Option<&Element>, mut f: F) -> Result<Array>
+    where
+        F: FnMut(&Element, &Element) -> Result<Element>,
+    {
+        // scalars reduce to themselves
+        if self.is_scalar() {
+            return Ok(self.clone());
+        }
+
+        if axis >= self.shape.len() {
+            return Err(anyhow!(ErrorKind::DomainError {
+                why: format!("no axis {} in an array of rank {}", axis, self.shape.len()),
+            }));
+        }
+
+        let length = self.shape[axis];
+        let outer = self.shape[..axis].iter().product::<usize>();
+        let inner = self.shape[axis + 1..].iter().product::<usize>();
+
+        let mut shape = self.shape.clone();
+        shape.remove(axis);
+
+        let mut data = Vec::with_capacity(outer * inner);
+        for o in 0..outer {
+            for i in 0..inner {
+                let mut elements = (0..length)
+                    .rev()
+                    .map(|k| &self.data[(o * length + k) * inner + i]);
+
+                let mut acc = match (elements.next(), identity) {
+                    (Some(last), _) => last.clone(),
+                    (None, Some(identity)) => identity.clone(),
+                    (None, None) => {
+                        return Err(anyhow!(ErrorKind::DomainError {
+                            why: String::from("can't reduce an empty axis without an identity"),
+                        }))
+                    }
+                };
+                for element in elements {
+                    acc = f(element, &acc)?;
+                }
+
+                data.push(acc);
+            }
+        }
+
+        Ok(Array { shape, data })
+    }
+
+    /// The ⍀ operator, scanning along the leading axis.
+    pub fn scan<F>(&self, associative: bool, f: F) -> Result<Array>
+    where
+        F: FnMut(&Element, &Element) -> Result<Element>,
+    {
+        self.scan_axis(0, associative, f)
+    }
+
+    /// Get every reduction of the prefixes along an axis, giving an array of the same shape.
+    ///
+    /// Each prefix is reduced from right to left like fold_axis, which takes quadratic time. If f is associative, each
+    /// reduction is found from the one before it instead.
+    pub fn scan_axis<F>(&self, axis: usize, associative: bool, mut f: F) -> Result<Array>
+    where
+        F: FnMut(&Element, &Element) -> Result<Element>,
+    {
+        // scalars scan to themselves
+        if self.is_scalar() {
+            return Ok(self.clone());
+        }
+
+        if axis >= self.shape.len() {
+            return Err(anyhow!(ErrorKind::DomainError {
+                why: format!("no axis {} in an array of rank {}", axis, self.shape.len()),
+            }));
+        }
+
+        let length = self.shape[axis];
+        let outer = self.shape[..axis].iter().product::<usize>();
+        let inner = self.shape[axis + 1..].iter().product::<usize>();
+
+        let mut data = self.data.clone();
+        for o in 0..outer {
+            for i in 0..inner {
+                let index = |k: usize| (o * length + k) * inner + i;
+
+                for k in 1..length {
+                    data[index(k)] = if associative {
+                        f(&data[index(k - 1)], &self.data[index(k)])?
+                    } else {
+                        let mut acc = self.data[index(k)].clone();
+                        for j in (0..k).rev() {
+                            acc = f(&self.data[index(j)], &acc)?;
+                        }
+                        acc
+                    };
+                }
+            }
+        }
+
+        Ok(Array {
+            shape: self.shape.clone(),
+            data,
+        })
+    }
+
+    /// Get the prototype of the array's first element, or zero if the array is empty.
+    pub fn prototype(&self) -> Element {
+        self.data
+            .first()
+            .map(Element::prototype)
+            .unwrap_or(Element::Int(0))
+    }
+
+    /// Give the data of the array a new shape, cycling through it if there isn't enough. Empty arrays are filled
+    /// with their prototype.
+    pub fn reshape(&self, shape: &[usize]) -> Result<Array> {
+        let size = Array::size(shape)?;
+        let data = if self.data.is_empty() {
+            vec![self.prototype(); size]
+        } else {
+            self.data.iter().cycle().take(size).cloned().collect()
+        };
+
+        Ok(Array {
+            shape: shape.to_vec(),
+            data,
+        })
+    }
+
+    /// Join two arrays along an axis of the result.
+    ///
+    /// An argument with one less axis than the other is joined as a single cell along the axis, and a scalar is
+    /// extended to fill a cell.
+    pub fn catenate(&self, other: &Array, axis: usize) -> Result<Array> {
+        let rank = self.shape.len().max(other.shape.len()).max(axis + 1);
+
+        // the shape of an argument with the axis put back in, if it's missing
+        let extend = |array: &Array| -> Result<Option<Vec<usize>>> {
+            if array.is_scalar() {
+                Ok(None)
+            } else if array.shape.len() == rank {
+                Ok(Some(array.shape.clone()))
+            } else if array.shape.len() + 1 == rank {
+                let mut shape = array.shape.clone();
+                shape.insert(axis, 1);
+                Ok(Some(shape))
+            } else {
+                Err(anyhow!(ErrorKind::RankError {
+                    why: format!(
+                        "can't join arrays of rank {} and {}",
+                        self.shape.len(),
+                        other.shape.len()
+                    ),
+                }))
+            }
+        };
+
+        let (mine, theirs) = match (extend(self)?, extend(other)?) {
+            (Some(mine), Some(theirs)) => (mine, theirs),
+            (Some(mine), None) => {
+                let mut theirs = mine.clone();
+                theirs[axis] = 1;
+                (mine, theirs)
+            }
+            (None, Some(theirs)) => {
+                let mut mine = theirs.clone();
+                mine[axis] = 1;
+                (mine, theirs)
+            }
+            (None, None) => (vec![1; rank], vec![1; rank]),
+        };
+
+        let agree = (0..rank).all(|i| i == axis || mine[i] == theirs[i]);
+        if !agree {
+            return Err(anyhow!(ErrorKind::LengthError {
+                why: format!("shapes {:?} and {:?} don't match", self.shape, other.shape),
+            }));
+        }
+
+        let mine = self.reshape(&mine)?;
+        let theirs = other.reshape(&theirs)?;
+
+        // take a cell from each in turn
+        let outer = mine.shape[..axis].iter().product::<usize>();
+        let my_cell = mine.data.len().checked_div(outer).unwrap_or(0);
+        let their_cell = theirs.data.len().checked_div(outer).unwrap_or(0);
+
+        let mut data = Vec::with_capacity(mine.data.len() + theirs.data.len());
+        for o in 0..outer {
+            data.extend_from_slice(&mine.data[o * my_cell..(o + 1) * my_cell]);
+            data.extend_from_slice(&theirs.data[o * their_cell..(o + 1) * their_cell]);
+        }
+
+        let mut shape = mine.shape;
+        shape[axis] += theirs.shape[axis];
+        Ok(Array { shape, data })
+    }
+
+    /// Join two arrays along a new axis, which comes before the existing axis numbered axis. This is the same as
+    /// catenating along the fractional axis axis-0.5.
+    ///
+    /// The arguments must have the same shape, unless one is a scalar.
+    pub fn laminate(&self, other: &Array, axis: usize) -> Result<Array> {
+        let shape = if self.is_scalar() {
+            other.shape.clone()
+        } else {
+            self.shape.clone()
+        };
+
+        if !other.is_scalar() && other.shape != shape {
+            return Err(anyhow!(ErrorKind::LengthError {
+                why: format!("shapes {:?} and {:?} don't match", self.shape, other.shape),
+            }));
+        }
+
+        if axis > shape.len() {
+            return Err(anyhow!(ErrorKind::RankError {
+                why: format!(
+                    "no axis {} to laminate before in an array of rank {}",
+                    axis,
+                    shape.len()
+                ),
+            }));
+        }
+
+        let mut cell = shape;
+        cell.insert(axis, 1);
+        self.reshape(&cell)?.catenate(&other.reshape(&cell)?, axis)
+    }
+
+    /// Select from an array, with one index for each of its leading axes.
+    ///
+    /// A scalar index picks a single position and removes its axis, an array of indices picks several and puts its
+    /// own axes in place of the axis, and None or a missing index picks everything along the axis. Indices count
+    /// from origin.
+    pub fn index(&self, indices: &[Option<Array>], origin: i64) -> Result<Array> {
+        if indices.len() > self.shape.len() {
+            return Err(anyhow!(ErrorKind::RankMismatch {
+                rank: self.shape.len(),
+                picked: indices.len(),
+            }));
+        }
+
+        let mut shape = Vec::new();
+        let mut offsets = vec![0];
+        for (axis, &length) in self.shape.iter().enumerate() {
+            let stride = self.shape[axis + 1..].iter().product::<usize>();
+
+            let positions = match indices.get(axis) {
+                Some(Some(index)) => {
+                    shape.extend_from_slice(&index.shape);
+                    index
+                        .data
+                        .iter()
+                        .map(|element| position(element, origin, length))
+                        .collect::<Result<Vec<_>>>()?
+                }
+                _ => {
+                    shape.push(length);
+                    (0..length).collect()
+                }
+            };
+
+            offsets = offsets
                 .iter()
-                .zip(array.data.iter())
-                .map(|(my, their)| f(my, their))
+                .flat_map(|offset| {
+                    positions
+                        .iter()
+                        .map(move |position| offset + position * stride)
+                })
                 .collect();
+        }
 
-            Array {
-                shape: self.shape.clone(),
-                data,
+        Ok(Array {
+            shape,
+            data: offsets
+                .into_iter()
+                .map(|offset| self.data[offset].clone())
+                .collect(),
+        })
+    }
+
+    /// Reorder the axes of an array, so that each axis of the array becomes the axis of the result that its entry in
+    /// axes says.
+    ///
+    /// There must be one entry in axes for each axis, and together they must cover 0 up to some rank. Axes that
+    /// become the same axis take the diagonal along them.
+    pub fn transpose(&self, axes: &[usize]) -> Result<Array> {
+        let rank = self.shape.len();
+        if axes.len() != rank {
+            return Err(anyhow!(ErrorKind::LengthError {
+                why: format!(
+                    "can't transpose an array of rank {} with {} axes",
+                    rank,
+                    axes.len()
+                ),
+            }));
+        }
+
+        if let Some(&axis) = axes.iter().find(|&&axis| axis >= rank) {
+            return Err(anyhow!(ErrorKind::RankError {
+                why: format!("no axis {} in an array of rank {}", axis, rank),
+            }));
+        }
+
+        let result_rank = axes.iter().map(|&axis| axis + 1).max().unwrap_or(0);
+        if let Some(missing) = (0..result_rank).find(|axis| !axes.contains(axis)) {
+            return Err(anyhow!(ErrorKind::DomainError {
+                why: format!("axis {} is missing from the transpose", missing),
+            }));
+        }
+
+        // axes that become the same axis step along together and are as long as the shortest of them
+        let mut shape = vec![usize::MAX; result_rank];
+        let mut strides = vec![0; result_rank];
+        for (axis, &to) in axes.iter().enumerate() {
+            shape[to] = shape[to].min(self.shape[axis]);
+            strides[to] += self.shape[axis + 1..].iter().product::<usize>();
+        }
+
+        let length = shape.iter().product::<usize>();
+        let mut data = Vec::with_capacity(length);
+        let mut index = vec![0; result_rank];
+        let mut offset = 0;
+        for _ in 0..length {
+            data.push(self.data[offset].clone());
+
+            // step to the next index like an odometer, keeping the offset in step with it
+            for axis in (0..result_rank).rev() {
+                index[axis] += 1;
+                offset += strides[axis];
+                if index[axis] < shape[axis] {
+                    break;
+                }
+                offset -= strides[axis] * index[axis];
+                index[axis] = 0;
             }
-        } else if array.shape[0] == 1 {
-            let data = self.data.iter().map(|my| f(my, &array.data[0])).collect();
+        }
 
-            Array {
-                shape: self.shape.clone(),
-                data,
+        Ok(Array { shape, data })
+    }
+
+    /// Reverse the order of an array along an axis.
+    pub fn reverse(&self, axis: usize) -> Array {
+        if axis >= self.shape.len() {
+            return self.clone();
+        }
+
+        let length = self.shape[axis];
+        let inner = self.shape[axis + 1..].iter().product::<usize>();
+        let data = (0..self.data.len())
+            .map(|offset| {
+                let along = offset / inner % length;
+                self.data[offset + (length - 1 - along) * inner - along * inner].clone()
+            })
+            .collect();
+
+        Array {
+            shape: self.shape.clone(),
+            data,
+        }
+    }
+
+    /// Rotate an array along an axis, so that each line along it starts amount places further in, wrapping around.
+    /// Negative amounts rotate the other way.
+    ///
+    /// The amounts are a scalar for every line, or an array with the shape of the array without the axis, giving
+    /// each line its own amount.
+    pub fn rotate(&self, amounts: &Array, axis: usize) -> Result<Array> {
+        let lines: Vec<usize> = self
+            .shape
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != axis)
+            .map(|(_, &length)| length)
+            .collect();
+        if !amounts.is_singleton() && amounts.shape != lines {
+            return Err(anyhow!(ErrorKind::LengthError {
+                why: format!(
+                    "can't rotate lines of {:?} by amounts of shape {:?}",
+                    lines, amounts.shape
+                ),
+            }));
+        }
+
+        let amounts = amounts
+            .data
+            .iter()
+            .map(|amount| {
+                amount.as_integer().ok_or_else(|| {
+                    anyhow!(ErrorKind::DomainError {
+                        why: format!("can only rotate by integers, not {}", amount),
+                    })
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if axis >= self.shape.len() {
+            return Ok(self.clone());
+        }
+
+        let length = self.shape[axis];
+        let inner = self.shape[axis + 1..].iter().product::<usize>();
+        let data = (0..self.data.len())
+            .map(|offset| {
+                let (outer, along, within) = (
+                    offset / inner / length,
+                    offset / inner % length,
+                    offset % inner,
+                );
+                let amount = amounts[if amounts.len() == 1 {
+                    0
+                } else {
+                    outer * inner + within
+                }];
+                let from = (along as i64 + amount.rem_euclid(length as i64)) % length as i64;
+                let from = from as usize;
+                self.data[offset + from * inner - along * inner].clone()
+            })
+            .collect();
+
+        Ok(Array {
+            shape: self.shape.clone(),
+            data,
+        })
+    }
+
+    /// Take count elements along each leading axis, from the start or from the end if the count is negative. Taking
+    /// more than there is pads the array with its prototype. Axes without a count are kept whole, and a scalar is
+    /// treated as having as many axes as there are counts.
+    pub fn take(&self, counts: &[i64]) -> Result<Array> {
+        let array = self.extend_rank(counts.len(), "take")?;
+        let mut starts = vec![0; array.shape.len()];
+        let mut shape = array.shape.clone();
+        for (axis, &count) in counts.iter().enumerate() {
+            shape[axis] = count.unsigned_abs() as usize;
+            if count < 0 {
+                starts[axis] = array.shape[axis] as i64 + count;
             }
-        } else if self.shape[0] == 1 {
-            let data = array
-                .data
+        }
+
+        Array::size(&shape)?;
+        Ok(array.window(&starts, shape))
+    }
+
+    /// Drop count elements along each leading axis, from the start or from the end if the count is negative. Axes
+    /// without a count are kept whole, and a scalar is treated as having as many axes as there are counts.
+    pub fn drop(&self, counts: &[i64]) -> Result<Array> {
+        let array = self.extend_rank(counts.len(), "drop")?;
+        let mut starts = vec![0; array.shape.len()];
+        let mut shape = array.shape.clone();
+        for (axis, &count) in counts.iter().enumerate() {
+            shape[axis] = array.shape[axis].saturating_sub(count.unsigned_abs() as usize);
+            if count > 0 {
+                starts[axis] = array.shape[axis] as i64 - shape[axis] as i64;
+            }
+        }
+
+        Ok(array.window(&starts, shape))
+    }
+
+    // Make sure an array has at least rank axes, giving a scalar as many as it needs.
+    fn extend_rank(&self, rank: usize, verb: &str) -> Result<Array> {
+        if rank <= self.shape.len() {
+            Ok(self.clone())
+        } else if self.is_scalar() {
+            self.reshape(&vec![1; rank])
+        } else {
+            Err(anyhow!(ErrorKind::RankError {
+                why: format!(
+                    "can't {} along {} axes of an array of rank {}",
+                    verb,
+                    rank,
+                    self.shape.len()
+                ),
+            }))
+        }
+    }
+
+    // Cut out a window of an array of some shape, starting at an index that may be outside of it. Any part of the
+    // window outside the array is filled with the prototype.
+    fn window(&self, starts: &[i64], shape: Vec<usize>) -> Array {
+        let fill = self.prototype();
+        let length = shape.iter().product::<usize>();
+        let data = (0..length)
+            .map(|offset| {
+                let mut from = 0;
+                for ((index, start), &length) in unravel(offset, &shape)
+                    .into_iter()
+                    .zip(starts)
+                    .zip(&self.shape)
+                {
+                    let index = start + index as i64;
+                    if index < 0 || index >= length as i64 {
+                        return fill.clone();
+                    }
+                    from = from * length + index as usize;
+                }
+                self.data[from].clone()
+            })
+            .collect();
+
+        Array { shape, data }
+    }
+
+    /// Compare two arrays in the total array order, which compares their major cells in turn. Ties go to the array
+    /// with fewer major cells, then lower rank, then smaller shape.
+    pub fn total_cmp(&self, other: &Array) -> Ordering {
+        order_cells(
+            (&self.shape, &self.data),
+            (&other.shape, &other.data),
+            &leaf_order,
+        )
+    }
+
+    /// Get the permutation of major cells that puts them in the total array order, counting from origin. Equal cells
+    /// keep their order.
+    pub fn grade(&self, descending: bool, origin: i64) -> Result<Array> {
+        self.grade_by(descending, origin, &leaf_order)
+    }
+
+    /// Grade an array like [grade](Array::grade), except that strings are compared by where their characters appear
+    /// in a collation sequence. Characters that aren't in it come after all those that are.
+    pub fn grade_collated(
+        &self,
+        collation: &[char],
+        descending: bool,
+        origin: i64,
+    ) -> Result<Array> {
+        let rank = |c: char| {
+            collation
                 .iter()
-                .map(|their| f(&self.data[0], their))
-                .collect();
+                .position(|&d| d == c)
+                .unwrap_or(collation.len())
+        };
 
-            Array {
-                shape: array.shape.clone(),
-                data,
+        self.grade_by(descending, origin, &|left, right| match (left, right) {
+            (Element::String(l), Element::String(r)) => {
+                l.chars().map(rank).cmp(r.chars().map(rank))
+            }
+            (l, r) => leaf_order(l, r),
+        })
+    }
+
+    /// Sort the major cells of an array into the total array order.
+    pub fn sort(&self, descending: bool) -> Result<Array> {
+        self.index(&[Some(self.grade(descending, 0)?)], 0)
+    }
+
+    fn grade_by(&self, descending: bool, origin: i64, leaf: &Leaf<'_>) -> Result<Array> {
+        let (&count, cell_shape) = self.shape.split_first().ok_or_else(|| {
+            anyhow!(ErrorKind::RankError {
+                why: String::from("can't grade a scalar"),
+            })
+        })?;
+
+        let size = cell_shape.iter().product::<usize>();
+        let cell = |i: usize| (cell_shape, &self.data[i * size..(i + 1) * size]);
+
+        let mut permutation: Vec<usize> = (0..count).collect();
+        permutation.sort_by(|&i, &j| {
+            let ordering = order_cells(cell(i), cell(j), leaf);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
             }
+        });
+
+        Ok(Array::vector(
+            permutation
+                .into_iter()
+                .map(|i| Element::Int(i as i64 + origin))
+                .collect(),
+        ))
+    }
+
+    /// Find where each cell of other first appears among the major cells of the array, counting from origin. Cells
+    /// that don't appear get one past the last index.
+    ///
+    /// The major cells are hashed, so this takes time proportional to the size of both arrays.
+    pub fn index_of(&self, other: &Array, origin: i64) -> Result<Array> {
+        let table = self.table();
+        let (shape, cells) = other.cells_shaped(&table.shape[1..])?;
+
+        let mut first = Cells::default();
+        table.major_cells().for_each(|cell| first.insert(cell));
+
+        let missing = table.shape[0];
+        let data = cells
+            .map(|cell| {
+                let i = first.find(cell).unwrap_or(missing);
+                Element::Int(i as i64 + origin)
+            })
+            .collect();
+
+        Ok(Array { shape, data })
+    }
+
+    /// The monadic ∪ function: the major cells of an array without repeats, in the order they first appear.
+    pub fn unique(&self) -> Array {
+        let table = self.table();
+        let mut seen = Cells::default();
+        let cells = table
+            .major_cells()
+            .filter(|cell| {
+                let repeat = seen.find(cell).is_some();
+                if !repeat {
+                    seen.insert(cell);
+                }
+                !repeat
+            })
+            .collect();
+        from_cells(&table.shape[1..], cells)
+    }
+
+    /// The dyadic ∪ function: the major cells of the array, followed by the cells of other that aren't among them.
+    /// Cells repeated in other are kept.
+    pub fn union(&self, other: &Array) -> Result<Array> {
+        let table = self.table();
+        let cells = other.cell_list(&table.shape[1..])?;
+        let mut seen = Cells::default();
+        table.major_cells().for_each(|cell| seen.insert(cell));
+        let cells = table
+            .major_cells()
+            .chain(cells.filter(|cell| seen.find(cell).is_none()))
+            .collect();
+        Ok(from_cells(&table.shape[1..], cells))
+    }
+
+    /// The ∩ function: the major cells of the array that are also cells of other.
+    pub fn intersection(&self, other: &Array) -> Result<Array> {
+        self.filter_cells(other, true)
+    }
+
+    /// The dyadic ~ function: the major cells of the array that aren't cells of other.
+    pub fn without(&self, other: &Array) -> Result<Array> {
+        self.filter_cells(other, false)
+    }
+
+    /// The dyadic ∊ function: whether each element of the array is an element of other.
+    pub fn member(&self, other: &Array) -> Array {
+        let mut elements = Cells::default();
+        other.cells(1).for_each(|cell| elements.insert(cell));
+        self.for_each_monad(|element| {
+            let found = elements.find(std::slice::from_ref(element)).is_some();
+            Element::Int(found as i64)
+        })
+    }
+
+    // Keep the major cells of the array that are or aren't cells of other.
+    fn filter_cells(&self, other: &Array, keep: bool) -> Result<Array> {
+        let table = self.table();
+        let cells = other.cell_list(&table.shape[1..])?;
+        let mut others = Cells::default();
+        cells.for_each(|cell| others.insert(cell));
+        let kept = table
+            .major_cells()
+            .filter(|cell| others.find(cell).is_some() == keep)
+            .collect();
+        Ok(from_cells(&table.shape[1..], kept))
+    }
+
+    // Get the array as a table of major cells, which makes a scalar a vector.
+    fn table(&self) -> Array {
+        if self.is_scalar() {
+            self.ravel()
         } else {
-            unreachable!("shape unsound");
+            self.clone()
         }
     }
 
-    /// The / operator
-    pub fn fold<F>(&self, f: F) -> Element
-    where
-        F: Fn(Element, &Element) -> Element,
-    {
-        // TODO this isn't exactly correct, this is only supposed to operate on the top rank
-        self.data
+    // Split the array into cells of a shape, which must be the shape of its last axes. Also gives the shape of the
+    // axes before them.
+    fn cells_shaped(
+        &self,
+        cell_shape: &[usize],
+    ) -> Result<(Vec<usize>, impl Iterator<Item = &[Element]>)> {
+        let frame = self.shape.len().checked_sub(cell_shape.len());
+        match frame {
+            Some(frame) if self.shape[frame..] == *cell_shape => Ok((
+                self.shape[..frame].to_vec(),
+                self.cells(cell_shape.iter().product()),
+            )),
+            _ => Err(anyhow!(ErrorKind::LengthError {
+                why: format!(
+                    "can't look for cells of {:?} in cells of shape {:?}",
+                    self.shape, cell_shape
+                ),
+            })),
+        }
+    }
+
+    // Split the array into a list of cells of a shape, like cells_shaped, so it can have at most one more axis than
+    // them.
+    fn cell_list(&self, cell_shape: &[usize]) -> Result<impl Iterator<Item = &[Element]>> {
+        if self.shape.len() > cell_shape.len() + 1 {
+            return Err(anyhow!(ErrorKind::RankError {
+                why: format!(
+                    "an array of rank {} isn't a list of cells of rank {}",
+                    self.shape.len(),
+                    cell_shape.len()
+                ),
+            }));
+        }
+
+        Ok(self.cells_shaped(cell_shape)?.1)
+    }
+
+    // Get the major cells of an array, which are its items along the first axis.
+    fn major_cells(&self) -> impl Iterator<Item = &[Element]> {
+        let size = self.shape[1..].iter().product();
+        self.cells(size)
+    }
+
+    // Split the data of an array into cells of a size.
+    fn cells(&self, size: usize) -> impl Iterator<Item = &[Element]> {
+        let count = self.data.len().checked_div(size).unwrap_or(0);
+        (0..count).map(move |i| &self.data[i * size..(i + 1) * size])
+    }
+
+    /// The ⍸ function: the index of each element, repeated as many times as the element says. Elements of arrays
+    /// that aren't vectors are indexed by vectors of indices. Indices count from origin.
+    pub fn positions(&self, origin: i64) -> Result<Array> {
+        let counts = self
+            .data
+            .iter()
+            .map(|element| match element.as_integer() {
+                Some(count) if count >= 0 => Ok(count as usize),
+                _ => Err(anyhow!(ErrorKind::DomainError {
+                    why: String::from("can only find the positions of non-negative integers"),
+                })),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // count the positions before making them, so there can't be too many
+        let total = counts
             .iter()
-            .skip(1)
-            .fold(self.data[0].clone(), |acc, next| f(acc, next))
+            .try_fold(0usize, |total, &count| total.checked_add(count))
+            .unwrap_or(usize::MAX);
+        let mut data = Vec::with_capacity(Array::size(&[total])?);
+
+        for (offset, &count) in counts.iter().enumerate() {
+            let index: Vec<Element> = unravel(offset, &self.shape)
+                .into_iter()
+                .map(|i| Element::Int(i as i64 + origin))
+                .collect();
+            let index = match &index[..] {
+                [i] if self.shape.len() == 1 => i.clone(),
+                _ => Element::Array(Array::vector(index)),
+            };
+
+            data.extend(std::iter::repeat_n(index, count));
+        }
+
+        Ok(Array::vector(data))
+    }
+
+    // monad ,
+    pub fn ravel(&self) -> Array {
+        Array {
+            shape: vec![self.data.len()],
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Make an array of integers with a shape, for tests.
+#[cfg(test)]
+pub(crate) fn ints(shape: &[usize], data: &[i64]) -> Array {
+    Array::from_parts(
+        shape.to_vec(),
+        data.iter().copied().map(Element::Int).collect(),
+    )
+}
+
+// Put cells of a shape together into an array along a new first axis.
+fn from_cells(cell_shape: &[usize], cells: Vec<&[Element]>) -> Array {
+    let mut shape = vec![cells.len()];
+    shape.extend_from_slice(cell_shape);
+    Array {
+        shape,
+        data: cells.concat(),
+    }
+}
+
+// Cells of arrays, hashed so that a cell the same as one of them can be found quickly.
+//
+// Floats that are the same can have different bits, so floats are hashed by which bucket of nearby floats they're in.
+// A float close to the edge of its bucket could be the same as a float in the next bucket, so finding a cell looks in
+// the next bucket too. Sameness isn't transitive, so it isn't a hash map's equality: the buckets only narrow down
+// which cells to compare.
+#[derive(Default)]
+struct Cells<'a> {
+    buckets: HashMap<u64, Vec<usize>>,
+    cells: Vec<&'a [Element]>,
+}
+
+impl<'a> Cells<'a> {
+    fn insert(&mut self, cell: &'a [Element]) {
+        let key = keys(cell, false)[0];
+        self.buckets.entry(key).or_default().push(self.cells.len());
+        self.cells.push(cell);
+    }
+
+    // Find the first cell that's the same as a cell.
+    fn find(&self, cell: &[Element]) -> Option<usize> {
+        keys(cell, true)
+            .iter()
+            .filter_map(|key| self.buckets.get(key))
+            .flatten()
+            .copied()
+            .filter(|&i| {
+                let other = self.cells[i];
+                other.len() == cell.len() && other.iter().zip(cell).all(|(l, r)| same(l, r))
+            })
+            .min()
+    }
+}
+
+// How many pieces of a cell go into its hash. Cells that only differ after them are told apart by comparing them,
+// and stopping there bounds how many neighbouring buckets there are to look in.
+const HASHED_PIECES: usize = 8;
+
+// How many floats there are in a bucket. Floats within the tolerance of each other are at most about 90 floats apart.
+const BUCKET_SIZE: i64 = 1 << 12;
+
+// How close to the edge of its bucket a float has to be to maybe be the same as a float in the next bucket.
+const BUCKET_EDGE: i64 = 1 << 8;
+
+// A piece of the hash of a cell: a hash of something compared exactly, or the bucket of a float along with the next
+// bucket if the float is close to it.
+enum Piece {
+    Exact(u64),
+    Bucket(i64, Option<i64>),
+}
+
+// Get the hash of a cell, or with near, all the hashes that a cell the same as it could have.
+fn keys(cell: &[Element], near: bool) -> Vec<u64> {
+    let mut pieces = Vec::new();
+    for element in cell {
+        add_pieces(element, &mut pieces);
+    }
+
+    let mut hashers = vec![DefaultHasher::new()];
+    for piece in pieces.iter().take(HASHED_PIECES) {
+        match *piece {
+            Piece::Exact(hash) => hashers.iter_mut().for_each(|state| hash.hash(state)),
+            Piece::Bucket(bucket, next) => {
+                let mut others = Vec::new();
+                if let (Some(next), true) = (next, near) {
+                    for state in &hashers {
+                        let mut other = state.clone();
+                        next.hash(&mut other);
+                        others.push(other);
+                    }
+                }
+                hashers.iter_mut().for_each(|state| bucket.hash(state));
+                hashers.extend(others);
+            }
+        }
+    }
+
+    hashers.iter().map(Hasher::finish).collect()
+}
+
+// Break an element into pieces to hash. Every number is bucketed as a float, since exact numbers are rounded to floats
+// to compare with them, so numbers that are the same are in the same bucket or next to each other.
+fn add_pieces(element: &Element, pieces: &mut Vec<Piece>) {
+    if pieces.len() >= HASHED_PIECES {
+        return;
+    }
+
+    let exact = |tag: u8, value: &dyn Fn(&mut DefaultHasher)| {
+        let mut state = DefaultHasher::new();
+        tag.hash(&mut state);
+        value(&mut state);
+        Piece::Exact(state.finish())
+    };
+
+    match element {
+        Element::Array(array) => {
+            pieces.push(exact(0, &|state| array.shape.hash(state)));
+            array
+                .data
+                .iter()
+                .for_each(|element| add_pieces(element, pieces));
+        }
+        Element::String(string) => pieces.push(exact(2, &|state| string.hash(state))),
+        Element::Atom(atom) => pieces.push(exact(3, &|state| atom.hash(state))),
+        Element::None => pieces.push(exact(4, &|_| ())),
+        number => {
+            let (re, im) = parts(number);
+            pieces.push(bucket(re));
+            pieces.push(bucket(im));
+        }
+    }
+}
+
+// Get the bucket of a float.
+fn bucket(f: f64) -> Piece {
+    // only zero is the same as zero, which includes -0, and infinities are only the same as themselves
+    if f == 0.0 {
+        return Piece::Bucket(0, None);
+    } else if !f.is_finite() {
+        return Piece::Bucket(f.to_bits() as i64, None);
     }
 
-    // monad ,
-    pub fn ravel(&self) -> Array {
-        Array {
-            shape: vec![self.data.len()],
-            data: self.data.clone(),
-        }
+    // order the bits of floats the same way as the floats
+    let bits = f.to_bits() as i64;
+    let ordered = if bits < 0 { i64::MIN - bits } else { bits };
+
+    let bucket = ordered.div_euclid(BUCKET_SIZE);
+    let offset = ordered.rem_euclid(BUCKET_SIZE);
+    let next = if offset < BUCKET_EDGE {
+        Some(bucket - 1)
+    } else if offset >= BUCKET_SIZE - BUCKET_EDGE {
+        Some(bucket + 1)
+    } else {
+        None
+    };
+    Piece::Bucket(bucket, next)
+}
+
+// Get the real and imaginary parts of a number as floats.
+fn parts(number: &Element) -> (f64, f64) {
+    match number {
+        Element::Int(i) => (*i as f64, 0.0),
+        Element::BigInt(i) => (i.to_f64().unwrap_or(f64::NAN), 0.0),
+        Element::Rational(r) => (r.to_f64().unwrap_or(f64::NAN), 0.0),
+        Element::Float(f) => (*f, 0.0),
+        Element::Complex(c) => (c.re, c.im),
+        _ => unreachable!("not a number"),
+    }
+}
+
+// Check if two elements are the same, looking inside arrays and comparing numbers as scalar::same does.
+fn same(left: &Element, right: &Element) -> bool {
+    match (left, right) {
+        (Element::Array(l), Element::Array(r)) => {
+            l.shape == r.shape && l.data.iter().zip(&r.data).all(|(l, r)| same(l, r))
+        }
+        (Element::Array(_), _) | (_, Element::Array(_)) => false,
+        (l, r) => scalar::same(l, r),
+    }
+}
+
+// An order on elements that aren't arrays.
+type Leaf<'a> = dyn Fn(&Element, &Element) -> Ordering + 'a;
+
+// Order elements that aren't arrays by kind, and then numbers by value, strings by their characters, and atoms by
+// name.
+fn leaf_order(left: &Element, right: &Element) -> Ordering {
+    let kind = |element: &Element| match element {
+        Element::String(_) => 1,
+        Element::Atom(_) => 2,
+        Element::None => 3,
+        _ => 0,
+    };
+
+    match (left, right) {
+        (Element::String(l), Element::String(r)) => l.cmp(r),
+        (Element::Atom(l), Element::Atom(r)) => l.name().cmp(r.name()),
+        (l, r) if kind(l) == 0 && kind(r) == 0 => scalar::order(l, r),
+        (l, r) => kind(l).cmp(&kind(r)),
+    }
+}
+
+// Order elements, comparing arrays cell by cell and everything else with leaf.
+fn order(left: &Element, right: &Element, leaf: &Leaf<'_>) -> Ordering {
+    match (left, right) {
+        (Element::Array(l), Element::Array(r)) => {
+            order_cells((&l.shape, &l.data), (&r.shape, &r.data), leaf)
+        }
+        (Element::Array(l), r) => {
+            order_cells((&l.shape, &l.data), (&[], std::slice::from_ref(r)), leaf)
+        }
+        (l, Element::Array(r)) => {
+            order_cells((&[], std::slice::from_ref(l)), (&r.shape, &r.data), leaf)
+        }
+        (l, r) => leaf(l, r),
+    }
+}
+
+// Order two arrays, given by their shape and data, by their major cells in turn. A scalar is its own only major cell.
+fn order_cells(
+    left: (&[usize], &[Element]),
+    right: (&[usize], &[Element]),
+    leaf: &Leaf<'_>,
+) -> Ordering {
+    if left.0.is_empty() && right.0.is_empty() {
+        return order(&left.1[0], &right.1[0], leaf);
+    }
+
+    let split = |shape: &[usize]| match shape.split_first() {
+        Some((&count, cell)) => (count, cell.to_vec(), cell.iter().product::<usize>()),
+        None => (1, Vec::new(), 1),
+    };
+    let (left_count, left_cell, left_size) = split(left.0);
+    let (right_count, right_cell, right_size) = split(right.0);
+
+    for i in 0..left_count.min(right_count) {
+        let ordering = order_cells(
+            (&left_cell, &left.1[i * left_size..(i + 1) * left_size]),
+            (&right_cell, &right.1[i * right_size..(i + 1) * right_size]),
+            leaf,
+        );
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    left_count
+        .cmp(&right_count)
+        .then(left.0.len().cmp(&right.0.len()))
+        .then_with(|| left.0.cmp(right.0))
+}
+
+// Turn an offset into the data of an array into an index along each of its axes.
+fn unravel(mut offset: usize, shape: &[usize]) -> Vec<usize> {
+    let mut index = vec![0; shape.len()];
+    for (axis, &length) in shape.iter().enumerate().rev() {
+        index[axis] = offset % length;
+        offset /= length;
+    }
+    index
+}
+
+// Turn an index into a position along an axis.
+fn position(index: &Element, origin: i64, length: usize) -> Result<usize> {
+    let wanted = index.as_integer().ok_or_else(|| {
+        anyhow!(ErrorKind::DomainError {
+            why: String::from("indices must be integers"),
+        })
+    })?;
+
+    match wanted.checked_sub(origin) {
+        Some(position) if position >= 0 && (position as usize) < length => Ok(position as usize),
+        _ => Err(anyhow!(ErrorKind::IndexOutOfBounds { wanted, length })),
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::identity_op)]
 mod test {
     use super::*;
 
+    fn add(l: &Element, r: &Element) -> Element {
+        match (l, r) {
+            (Element::Int(l), Element::Int(r)) => Element::Int(l + r),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn array1() {
         let array = Array {
@@ -197,16 +1446,18 @@ mod test {
             ],
         };
 
-        let out = array.for_each_dyad(
-            |l, r| match l {
-                Element::Int(l) => match r {
-                    Element::Int(r) => Element::Int(l + r),
+        let out = array
+            .for_each_dyad(
+                |l, r| match l {
+                    Element::Int(l) => match r {
+                        Element::Int(r) => Element::Int(l + r),
+                        _ => panic!(),
+                    },
                     _ => panic!(),
                 },
-                _ => panic!(),
-            },
-            &array,
-        );
+                &array,
+            )
+            .unwrap();
 
         assert_eq!(
             out,
@@ -233,7 +1484,15 @@ mod test {
             data: vec![Element::Int(4)],
         };
 
-        assert!(array2.is_scalar());
+        assert!(!array2.is_scalar());
+        assert!(array2.is_singleton());
+
+        let array3 = Array {
+            shape: vec![],
+            data: vec![Element::Int(4)],
+        };
+
+        assert!(array3.is_scalar());
     }
 
     #[test]
@@ -257,16 +1516,18 @@ mod test {
             data: vec![Element::Int(4)],
         };
 
-        let out = array.for_each_dyad(
-            |l, r| match l {
-                Element::Int(l) => match r {
-                    Element::Int(r) => Element::Int(l + r),
+        let out = array
+            .for_each_dyad(
+                |l, r| match l {
+                    Element::Int(l) => match r {
+                        Element::Int(r) => Element::Int(l + r),
+                        _ => panic!(),
+                    },
                     _ => panic!(),
                 },
-                _ => panic!(),
-            },
-            &array2,
-        );
+                &array2,
+            )
+            .unwrap();
 
         assert_eq!(
             out,
@@ -302,14 +1563,679 @@ mod test {
             ],
         };
 
-        let result = array.fold(|acc, next| match acc {
-            Element::Int(acc) => match next {
-                Element::Int(next) => Element::Int(acc + next),
-                _ => panic!(),
-            },
+        let fold_add = |next: &Element, acc: &Element| Ok(add(next, acc));
+
+        let result = array.fold(None, fold_add).unwrap();
+        assert_eq!(
+            result,
+            Array::vector(vec![
+                Element::Int(1 + 5),
+                Element::Int(2 + 6),
+                Element::Int(3 + 7),
+                Element::Int(4 + 8),
+            ])
+        );
+
+        let result = array.fold_axis(1, None, fold_add).unwrap();
+        assert_eq!(
+            result,
+            Array::vector(vec![
+                Element::Int(1 + 2 + 3 + 4),
+                Element::Int(5 + 6 + 7 + 8),
+            ])
+        );
+
+        let result = result.fold(None, fold_add).unwrap();
+        assert_eq!(
+            result,
+            Array::from_element(Element::Int(1 + 2 + 3 + 4 + 5 + 6 + 7 + 8))
+        );
+        assert_eq!(result.fold(None, fold_add).unwrap(), result);
+        assert!(array.fold_axis(2, None, fold_add).is_err());
+    }
+
+    #[test]
+    fn atom1() {
+        let person = Atom::new("person");
+        assert_eq!(person, Atom::new("person"));
+        assert_ne!(person, Atom::new("name"));
+        assert_eq!(person.name(), "person");
+        assert_eq!(person.to_string(), "∆person");
+        assert_ne!(
+            Element::Atom(person),
+            Element::String(String::from("person"))
+        );
+    }
+
+    #[test]
+    fn array6() {
+        // leading axis agreement, in both directions
+        let vector = ints(&[3], &[100, 200, 300]);
+        let matrix = ints(&[3, 2], &[1, 2, 3, 4, 5, 6]);
+        let sum = ints(&[3, 2], &[101, 102, 203, 204, 305, 306]);
+        assert_eq!(vector.for_each_dyad(add, &matrix).unwrap(), sum);
+        assert_eq!(matrix.for_each_dyad(add, &vector).unwrap(), sum);
+
+        // a 1×2 matrix isn't a scalar
+        let row = ints(&[1, 2], &[10, 20]);
+        assert!(row.for_each_dyad(add, &ints(&[2], &[1, 2])).is_err());
+        assert_eq!(
+            row.for_each_dyad(add, &ints(&[1], &[1])).unwrap(),
+            ints(&[1, 2], &[11, 21])
+        );
+
+        assert!(vector.for_each_dyad(add, &ints(&[2, 3], &[0; 6])).is_err());
+        assert!(vector.for_each_dyad(add, &ints(&[2], &[0; 2])).is_err());
+        assert_eq!(
+            ints(&[0], &[])
+                .for_each_dyad(add, &ints(&[0, 3], &[]))
+                .unwrap(),
+            ints(&[0, 3], &[])
+        );
+    }
+
+    #[test]
+    fn array7() {
+        let scalar = Array::from_element(Element::Int(5));
+        let singleton = Array::vector(vec![Element::Int(1)]);
+        assert!(scalar.is_scalar());
+        assert!(!singleton.is_scalar());
+        assert_eq!(scalar.shape(), &[] as &[usize]);
+        assert_eq!(scalar.relegate(), Some(Element::Int(5)));
+        assert_eq!(singleton.relegate(), None);
+        assert_eq!(scalar.ravel(), Array::vector(vec![Element::Int(5)]));
+
+        assert!(scalar.for_each_dyad(add, &scalar).unwrap().is_scalar());
+        assert_eq!(
+            scalar.for_each_dyad(add, &singleton).unwrap(),
+            Array::vector(vec![Element::Int(6)])
+        );
+        assert_eq!(
+            singleton.for_each_dyad(add, &scalar).unwrap(),
+            Array::vector(vec![Element::Int(6)])
+        );
+    }
+
+    #[test]
+    fn array8() {
+        // right to left: 1 - (2 - 3)
+        let subtract = |next: &Element, acc: &Element| match (next, acc) {
+            (Element::Int(next), Element::Int(acc)) => Ok(Element::Int(next - acc)),
             _ => panic!(),
-        });
+        };
+        let vector = Array::vector(vec![Element::Int(1), Element::Int(2), Element::Int(3)]);
+        assert_eq!(
+            vector.fold(None, subtract).unwrap(),
+            Array::from_element(Element::Int(2))
+        );
+
+        let empty = Array::from_parts(vec![0, 3], Vec::new());
+        assert!(empty.fold(None, subtract).is_err());
+        assert_eq!(
+            empty.fold(Some(&Element::Int(0)), subtract).unwrap(),
+            Array::vector(vec![Element::Int(0); 3])
+        );
+        assert_eq!(
+            empty.fold_axis(1, None, subtract).unwrap(),
+            Array::vector(Vec::new())
+        );
+    }
+
+    #[test]
+    fn array9() {
+        let calls = std::cell::Cell::new(0);
+        let subtract = |next: &Element, acc: &Element| {
+            calls.set(calls.get() + 1);
+            match (next, acc) {
+                (Element::Int(next), Element::Int(acc)) => Ok(Element::Int(next - acc)),
+                _ => panic!(),
+            }
+        };
+
+        let matrix = ints(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            matrix.scan_axis(1, false, subtract).unwrap(),
+            ints(&[2, 3], &[1, -1, 2, 4, -1, 5])
+        );
+        assert_eq!(
+            matrix.scan(false, subtract).unwrap(),
+            ints(&[2, 3], &[1, 2, 3, -3, -3, -3])
+        );
+
+        // an associative scan only calls f once per element after the first
+        let vector = ints(&[5], &[1, 2, 3, 4, 5]);
+        calls.set(0);
+        vector.scan(false, subtract).unwrap();
+        assert_eq!(calls.get(), 10);
+        calls.set(0);
+        vector.scan(true, subtract).unwrap();
+        assert_eq!(calls.get(), 4);
+
+        assert_eq!(
+            ints(&[0], &[]).scan(true, subtract).unwrap(),
+            ints(&[0], &[])
+        );
+    }
+
+    #[test]
+    fn array10() {
+        let vector = ints(&[3], &[1, 2, 3]);
+        assert_eq!(
+            vector.reshape(&[2, 4]).unwrap(),
+            ints(&[2, 4], &[1, 2, 3, 1, 2, 3, 1, 2])
+        );
+        assert_eq!(vector.reshape(&[2]).unwrap(), ints(&[2], &[1, 2]));
+        assert_eq!(vector.reshape(&[]).unwrap(), ints(&[], &[1]));
+        assert_eq!(vector.reshape(&[0, 5]).unwrap(), ints(&[0, 5], &[]));
+        assert_eq!(ints(&[0], &[]).reshape(&[2]).unwrap(), ints(&[2], &[0, 0]));
+        assert!(vector.reshape(&[Array::MAX_SIZE + 1]).is_err());
+        assert!(vector.reshape(&[1 << 32, 1 << 32]).is_err());
+
+        let strings = Array::vector(vec![Element::String(String::from("a"))]);
+        assert_eq!(strings.prototype(), Element::String(String::new()));
+        let nested = Array::vector(vec![Element::Array(vector), Element::Int(4)]);
+        assert_eq!(nested.prototype(), Element::Array(ints(&[3], &[0, 0, 0])));
+    }
+
+    #[test]
+    fn array11() {
+        let x = ints(&[2], &[1, 2]);
+        let y = ints(&[3], &[3, 4, 5]);
+        assert_eq!(x.catenate(&y, 0).unwrap(), ints(&[5], &[1, 2, 3, 4, 5]));
+        assert_eq!(
+            x.catenate(&ints(&[], &[9]), 0).unwrap(),
+            ints(&[3], &[1, 2, 9])
+        );
+        assert_eq!(
+            ints(&[], &[1]).catenate(&ints(&[], &[2]), 0).unwrap(),
+            ints(&[2], &[1, 2])
+        );
+
+        // a vector is a column when joined to the end of a matrix, and a row when joined to the start
+        let matrix = ints(&[2, 2], &[1, 2, 3, 4]);
+        assert_eq!(
+            matrix.catenate(&x, 1).unwrap(),
+            ints(&[2, 3], &[1, 2, 1, 3, 4, 2])
+        );
+        assert_eq!(
+            x.catenate(&matrix, 0).unwrap(),
+            ints(&[3, 2], &[1, 2, 1, 2, 3, 4])
+        );
+        assert_eq!(
+            matrix.catenate(&ints(&[], &[0]), 0).unwrap(),
+            ints(&[3, 2], &[1, 2, 3, 4, 0, 0])
+        );
+
+        assert!(matches!(
+            matrix.catenate(&y, 1).unwrap_err().downcast::<ErrorKind>(),
+            Ok(ErrorKind::LengthError { .. })
+        ));
+        assert!(matches!(
+            ints(&[1, 1, 1], &[1])
+                .catenate(&x, 0)
+                .unwrap_err()
+                .downcast::<ErrorKind>(),
+            Ok(ErrorKind::RankError { .. })
+        ));
+    }
+
+    #[test]
+    fn array12() {
+        let x = ints(&[3], &[1, 2, 3]);
+        let y = ints(&[3], &[4, 5, 6]);
+        assert_eq!(
+            x.laminate(&y, 0).unwrap(),
+            ints(&[2, 3], &[1, 2, 3, 4, 5, 6])
+        );
+        assert_eq!(
+            x.laminate(&y, 1).unwrap(),
+            ints(&[3, 2], &[1, 4, 2, 5, 3, 6])
+        );
+        assert_eq!(
+            x.laminate(&ints(&[], &[0]), 0).unwrap(),
+            ints(&[2, 3], &[1, 2, 3, 0, 0, 0])
+        );
+        assert!(x.laminate(&ints(&[2], &[1, 2]), 0).is_err());
+        assert!(x.laminate(&y, 2).is_err());
+    }
+
+    #[test]
+    fn array13() {
+        let scalar = |i: i64| Some(ints(&[], &[i]));
+
+        let matrix = ints(&[3, 5], &(1..=15).collect::<Vec<_>>());
+        assert_eq!(
+            matrix.index(&[scalar(2), scalar(4)], 1).unwrap(),
+            ints(&[], &[9])
+        );
+        assert_eq!(
+            matrix.index(&[scalar(2)], 1).unwrap(),
+            ints(&[5], &[6, 7, 8, 9, 10])
+        );
+        assert_eq!(
+            matrix.index(&[None, scalar(0)], 0).unwrap(),
+            ints(&[3], &[1, 6, 11])
+        );
+        assert_eq!(
+            matrix
+                .index(&[Some(ints(&[2], &[3, 1])), Some(ints(&[1], &[5]))], 1)
+                .unwrap(),
+            ints(&[2, 1], &[15, 5])
+        );
+        assert_eq!(matrix.index(&[], 1).unwrap(), matrix);
+
+        let vector = ints(&[4], &[1, 2, 3, 4]);
+        assert!(matches!(
+            vector
+                .index(&[scalar(8)], 1)
+                .unwrap_err()
+                .downcast::<ErrorKind>(),
+            Ok(ErrorKind::IndexOutOfBounds {
+                wanted: 8,
+                length: 4
+            })
+        ));
+        assert!(matches!(
+            vector
+                .index(&[scalar(0)], 1)
+                .unwrap_err()
+                .downcast::<ErrorKind>(),
+            Ok(ErrorKind::IndexOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            vector
+                .index(&[scalar(1), scalar(1)], 1)
+                .unwrap_err()
+                .downcast::<ErrorKind>(),
+            Ok(ErrorKind::RankMismatch { rank: 1, picked: 2 })
+        ));
+    }
+
+    #[test]
+    fn array14() {
+        assert_eq!(
+            ints(&[4], &[1, 0, 2, 0]).positions(0).unwrap(),
+            ints(&[3], &[0, 2, 2])
+        );
+        assert_eq!(
+            ints(&[2, 2], &[0, 1, 1, 0]).positions(1).unwrap(),
+            Array::vector(vec![
+                Element::Array(ints(&[2], &[1, 2])),
+                Element::Array(ints(&[2], &[2, 1])),
+            ])
+        );
+        assert!(ints(&[1], &[-1]).positions(0).is_err());
+    }
+
+    #[test]
+    fn array15() {
+        let matrix = ints(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            matrix.transpose(&[1, 0]).unwrap(),
+            ints(&[3, 2], &[1, 4, 2, 5, 3, 6])
+        );
+        assert_eq!(matrix.transpose(&[0, 1]).unwrap(), matrix);
+        assert_eq!(matrix.transpose(&[0, 0]).unwrap(), ints(&[2], &[1, 5]));
+
+        let cube = ints(&[2, 3, 4], &(0..24).collect::<Vec<_>>());
+        let turned = cube.transpose(&[2, 0, 1]).unwrap();
+        assert_eq!(turned.shape(), &[3, 4, 2]);
+        assert_eq!(turned.data()[..4], ints(&[4], &[0, 12, 1, 13]).data()[..]);
+        assert_eq!(
+            cube.transpose(&[1, 0, 0]).unwrap(),
+            ints(&[3, 2], &[0, 12, 5, 17, 10, 22])
+        );
+
+        let scalar = ints(&[], &[5]);
+        assert_eq!(scalar.transpose(&[]).unwrap(), scalar);
+        assert!(ints(&[0, 3], &[]).transpose(&[1, 0]).unwrap().shape() == [3, 0]);
+
+        assert!(matrix.transpose(&[0]).is_err());
+        assert!(matrix.transpose(&[0, 2]).is_err());
+        assert!(cube.transpose(&[0, 2, 2]).is_err());
+    }
+
+    #[test]
+    fn array16() {
+        let matrix = ints(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(matrix.reverse(1), ints(&[2, 3], &[3, 2, 1, 6, 5, 4]));
+        assert_eq!(matrix.reverse(0), ints(&[2, 3], &[4, 5, 6, 1, 2, 3]));
+        assert_eq!(ints(&[], &[5]).reverse(0), ints(&[], &[5]));
+        assert_eq!(ints(&[0], &[]).reverse(0), ints(&[0], &[]));
+
+        let vector = ints(&[5], &[1, 2, 3, 4, 5]);
+        assert_eq!(
+            vector.rotate(&ints(&[], &[2]), 0).unwrap(),
+            ints(&[5], &[3, 4, 5, 1, 2])
+        );
+        assert_eq!(
+            vector.rotate(&ints(&[], &[-1]), 0).unwrap(),
+            ints(&[5], &[5, 1, 2, 3, 4])
+        );
+        assert_eq!(
+            vector.rotate(&ints(&[], &[12]), 0).unwrap(),
+            ints(&[5], &[3, 4, 5, 1, 2])
+        );
+        // huge amounts are reduced before they're added to indices, so they can't overflow
+        assert_eq!(
+            vector.rotate(&ints(&[], &[i64::MIN]), 0).unwrap(),
+            ints(&[5], &[3, 4, 5, 1, 2])
+        );
+        assert_eq!(
+            vector.rotate(&ints(&[], &[i64::MAX]), 0).unwrap(),
+            ints(&[5], &[3, 4, 5, 1, 2])
+        );
+
+        assert_eq!(
+            matrix.rotate(&ints(&[2], &[1, -1]), 1).unwrap(),
+            ints(&[2, 3], &[2, 3, 1, 6, 4, 5])
+        );
+        assert_eq!(
+            matrix.rotate(&ints(&[3], &[0, 1, 2]), 0).unwrap(),
+            ints(&[2, 3], &[1, 5, 3, 4, 2, 6])
+        );
+        assert!(matrix.rotate(&ints(&[3], &[0, 1, 2]), 1).is_err());
+        assert!(matrix
+            .rotate(&Array::from_element(Element::Float(0.5)), 1)
+            .is_err());
+    }
+
+    #[test]
+    fn array17() {
+        let vector = ints(&[4], &[1, 2, 3, 4]);
+        assert_eq!(vector.take(&[2]).unwrap(), ints(&[2], &[1, 2]));
+        assert_eq!(vector.take(&[-2]).unwrap(), ints(&[2], &[3, 4]));
+        assert_eq!(vector.take(&[6]).unwrap(), ints(&[6], &[1, 2, 3, 4, 0, 0]));
+        assert_eq!(vector.take(&[-6]).unwrap(), ints(&[6], &[0, 0, 1, 2, 3, 4]));
+        assert_eq!(vector.take(&[0]).unwrap(), ints(&[0], &[]));
+        assert!(vector.take(&[i64::MIN]).is_err());
+        assert!(ints(&[2, 3], &[0; 6]).take(&[1 << 62]).is_err());
+        assert!(Array::size(&[1 << 32, 1 << 32]).is_err());
+        assert_eq!(vector.drop(&[1]).unwrap(), ints(&[3], &[2, 3, 4]));
+        assert_eq!(vector.drop(&[-3]).unwrap(), ints(&[1], &[1]));
+        assert_eq!(vector.drop(&[9]).unwrap(), ints(&[0], &[]));
+
+        let matrix = ints(&[3, 3], &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(
+            matrix.take(&[2]).unwrap(),
+            ints(&[2, 3], &[1, 2, 3, 4, 5, 6])
+        );
+        assert_eq!(
+            matrix.take(&[-2, 4]).unwrap(),
+            ints(&[2, 4], &[4, 5, 6, 0, 7, 8, 9, 0])
+        );
+        assert_eq!(matrix.drop(&[1, -1]).unwrap(), ints(&[2, 2], &[4, 5, 7, 8]));
+
+        assert_eq!(ints(&[], &[5]).take(&[3]).unwrap(), ints(&[3], &[5, 0, 0]));
+        assert_eq!(ints(&[], &[5]).drop(&[0, 0]).unwrap(), ints(&[1, 1], &[5]));
+        assert!(vector.take(&[1, 1]).is_err());
+
+        let strings = Array::vector(vec![Element::String(String::from("a"))]);
+        assert_eq!(
+            strings.take(&[2]).unwrap(),
+            Array::vector(vec![
+                Element::String(String::from("a")),
+                Element::String(String::new())
+            ])
+        );
+    }
+
+    #[test]
+    fn array18() {
+        assert_eq!(Array::range(4, 1), ints(&[4], &[1, 2, 3, 4]));
+        assert_eq!(Array::range(0, 0), ints(&[0], &[]));
+        assert_eq!(
+            Array::indices(&[2, 2], 0),
+            Array::from_parts(
+                vec![2, 2],
+                vec![
+                    Element::Array(ints(&[2], &[0, 0])),
+                    Element::Array(ints(&[2], &[0, 1])),
+                    Element::Array(ints(&[2], &[1, 0])),
+                    Element::Array(ints(&[2], &[1, 1])),
+                ]
+            )
+        );
+
+        let table = ints(&[5], &[10, 20, 30, 20, 40]);
+        assert_eq!(
+            table.index_of(&ints(&[3], &[20, 50, 10]), 1).unwrap(),
+            ints(&[3], &[2, 6, 1])
+        );
+        assert_eq!(
+            table
+                .index_of(&Array::from_element(Element::Float(30.0)), 0)
+                .unwrap(),
+            ints(&[], &[2])
+        );
+
+        let rows = ints(&[3, 2], &[1, 2, 3, 4, 1, 2]);
+        assert_eq!(
+            rows.index_of(&ints(&[3, 2], &[3, 4, 1, 2, 2, 1]), 1)
+                .unwrap(),
+            ints(&[3], &[2, 1, 4])
+        );
+        assert_eq!(
+            rows.index_of(&ints(&[2], &[3, 4]), 0).unwrap(),
+            ints(&[], &[1])
+        );
+        assert!(rows.index_of(&ints(&[3], &[1, 2, 3]), 0).is_err());
+    }
+
+    #[test]
+    fn array19() {
+        let string = |s: &str| Element::String(String::from(s));
+
+        let vector = ints(&[5], &[30, 10, 20, 10, 40]);
+        assert_eq!(
+            vector.grade(false, 1).unwrap(),
+            ints(&[5], &[2, 4, 3, 1, 5])
+        );
+        assert_eq!(vector.grade(true, 0).unwrap(), ints(&[5], &[4, 0, 2, 1, 3]));
+        assert_eq!(
+            vector.sort(false).unwrap(),
+            ints(&[5], &[10, 10, 20, 30, 40])
+        );
+        assert!(ints(&[], &[1]).grade(false, 0).is_err());
+
+        let matrix = ints(&[3, 2], &[2, 1, 1, 9, 2, 0]);
+        assert_eq!(matrix.grade(false, 0).unwrap(), ints(&[3], &[1, 2, 0]));
+
+        let mixed = Array::vector(vec![
+            Element::Atom(Atom::new("b")),
+            string("b"),
+            Element::Float(1.5),
+            Element::Array(ints(&[2], &[1, 2])),
+            string("a"),
+            Element::Int(1),
+        ]);
+        assert_eq!(
+            mixed.grade(false, 0).unwrap(),
+            ints(&[6], &[5, 3, 2, 4, 1, 0])
+        );
+        assert_eq!(
+            Element::Int(2).total_cmp(&Element::Float(2.0)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            ints(&[2], &[1, 2]).total_cmp(&ints(&[3], &[1, 2, 0])),
+            Ordering::Less
+        );
+
+        let words = Array::vector(vec![string("ba"), string("ab"), string("b"), string("c")]);
+        assert_eq!(words.grade(false, 0).unwrap(), ints(&[4], &[1, 2, 0, 3]));
+        assert_eq!(
+            words.grade_collated(&['c', 'b'], false, 0).unwrap(),
+            ints(&[4], &[3, 2, 0, 1])
+        );
+    }
+
+    #[test]
+    fn array20() {
+        let vector = ints(&[6], &[3, 1, 3, 2, 1, 4]);
+        assert_eq!(vector.unique(), ints(&[4], &[3, 1, 2, 4]));
+        assert_eq!(ints(&[], &[7]).unique(), ints(&[1], &[7]));
+
+        let matrix = ints(&[4, 2], &[1, 2, 3, 4, 1, 2, 5, 6]);
+        assert_eq!(matrix.unique(), ints(&[3, 2], &[1, 2, 3, 4, 5, 6]));
+        assert_eq!(
+            matrix.without(&ints(&[2], &[1, 2])).unwrap(),
+            ints(&[2, 2], &[3, 4, 5, 6])
+        );
+        assert!(matrix.without(&ints(&[3], &[1, 2, 3])).is_err());
+        // ⍵ is a list of cells like ⍺'s, so it can't have more than one axis before them
+        let tall = ints(&[1, 2, 2], &[1, 2, 7, 8]);
+        for result in [
+            matrix.union(&tall),
+            matrix.intersection(&tall),
+            matrix.without(&tall),
+            ints(&[3], &[1, 2, 3]).union(&ints(&[2, 2], &[1, 1, 1, 1])),
+        ] {
+            assert!(matches!(
+                result.unwrap_err().downcast::<ErrorKind>(),
+                Ok(ErrorKind::RankError { .. })
+            ));
+        }
+
+        let other = ints(&[3], &[4, 5, 1]);
+        assert_eq!(
+            vector.union(&other).unwrap(),
+            ints(&[7], &[3, 1, 3, 2, 1, 4, 5])
+        );
+        assert_eq!(vector.intersection(&other).unwrap(), ints(&[3], &[1, 1, 4]));
+        assert_eq!(vector.without(&other).unwrap(), ints(&[3], &[3, 3, 2]));
+        assert_eq!(
+            vector.without(&ints(&[], &[3])).unwrap(),
+            ints(&[4], &[1, 2, 1, 4])
+        );
+        assert_eq!(
+            ints(&[2, 2], &[1, 5, 4, 9]).member(&vector),
+            ints(&[2, 2], &[1, 0, 1, 0])
+        );
+
+        let floats = Array::vector(vec![
+            Element::Float(0.1 + 0.2),
+            Element::Float(0.3),
+            Element::Int(1),
+            Element::Float(1.0),
+            Element::Float(1.0 + 1e-10),
+        ]);
+        assert_eq!(
+            floats.unique(),
+            Array::vector(vec![
+                Element::Float(0.1 + 0.2),
+                Element::Int(1),
+                Element::Float(1.0 + 1e-10),
+            ])
+        );
+        assert_eq!(
+            floats
+                .index_of(&Array::from_element(Element::Float(0.3)), 0)
+                .unwrap(),
+            ints(&[], &[0])
+        );
+    }
+
+    #[test]
+    fn array21() {
+        assert_eq!(Element::Int(3).as_integer(), Some(3));
+        assert_eq!(Element::Float(-2.0).as_integer(), Some(-2));
+        assert_eq!(Element::Float(2.5).as_integer(), None);
+        assert_eq!(Element::Float(f64::INFINITY).as_integer(), None);
+        assert_eq!(
+            Element::Complex(Complex64::new(4.0, 0.0)).as_integer(),
+            Some(4)
+        );
+        assert_eq!(Element::String(String::from("1")).as_integer(), None);
+
+        let vector = ints(&[3], &[1, 2, 3]);
+        assert_eq!(
+            vector
+                .rotate(&Array::from_element(Element::Float(1.0)), 0)
+                .unwrap(),
+            ints(&[3], &[2, 3, 1])
+        );
+
+        // amounts are checked even when there's nothing to rotate
+        let scalar = ints(&[], &[5]);
+        assert_eq!(scalar.rotate(&ints(&[], &[1]), 0).unwrap(), scalar);
+        assert!(scalar
+            .rotate(&Array::from_element(Element::Float(0.5)), 0)
+            .is_err());
+        assert!(scalar.rotate(&ints(&[2], &[1, 2]), 0).is_err());
+    }
+
+    #[test]
+    fn array22() {
+        // ⍺∪⍵ is ⍺,⍵~⍺, so repeats in ⍵ are kept
+        assert_eq!(
+            ints(&[2], &[1, 2])
+                .union(&ints(&[4], &[3, 2, 3, 4]))
+                .unwrap(),
+            ints(&[5], &[1, 2, 3, 3, 4])
+        );
+
+        // floats on either side of the edge of a bucket, which are next to each other
+        let float = |bits: u64| Element::Float(f64::from_bits(bits));
+        for edge in [1.0f64.to_bits(), 1.5f64.to_bits(), (-3.0f64).to_bits()] {
+            let floats = Array::vector(vec![float(edge), float(edge - 1), float(edge + 1)]);
+            assert_eq!(floats.unique(), Array::vector(vec![float(edge)]));
+            assert_eq!(
+                floats
+                    .index_of(&Array::vector(vec![float(edge - 1), float(edge + 1)]), 0)
+                    .unwrap(),
+                ints(&[2], &[0, 0])
+            );
+            assert_eq!(
+                Array::vector(vec![float(edge - 1)]).member(&Array::vector(vec![float(edge)])),
+                ints(&[1], &[1])
+            );
+        }
 
-        assert_eq!(result, Element::Int(1 + 2 + 3 + 4 + 5 + 6 + 7 + 8));
+        // exact numbers are rounded to floats to compare with them, so 49×÷49 is the same as 1
+        let one = Element::Float(49.0 * (1.0 / 49.0));
+        let mixed = |data: &[Element]| Array::vector(data.to_vec());
+        assert_eq!(
+            Array::vector(vec![one.clone()]).member(&ints(&[2], &[1, 2])),
+            ints(&[1], &[1])
+        );
+        assert_eq!(
+            ints(&[2], &[1, 2])
+                .index_of(&Array::vector(vec![one.clone()]), 0)
+                .unwrap(),
+            ints(&[1], &[0])
+        );
+        assert_eq!(
+            mixed(&[Element::Int(1), Element::Float(1.0), one.clone()]).unique(),
+            ints(&[1], &[1])
+        );
+        assert_eq!(
+            Array::vector(vec![one.clone()])
+                .without(&ints(&[1], &[1]))
+                .unwrap(),
+            ints(&[0], &[])
+        );
+
+        // 2^53 and 2^53 + 1 are both the same as the float 2^53 but not each other, so the first one seen wins
+        let big = 1i64 << 53;
+        let (int, float, next) = (
+            Element::Int(big),
+            Element::Float(big as f64),
+            Element::Int(big + 1),
+        );
+        assert_eq!(
+            mixed(&[int.clone(), float.clone(), next.clone()]).unique(),
+            mixed(&[int.clone(), next.clone()])
+        );
+        assert_eq!(
+            mixed(&[float.clone(), int.clone(), next.clone()]).unique(),
+            Array::vector(vec![float.clone()])
+        );
+        assert_eq!(
+            mixed(&[int, next.clone()])
+                .index_of(&mixed(&[float, next]), 0)
+                .unwrap(),
+            ints(&[2], &[0, 1])
+        );
     }
 }
@@ -0,0 +1,176 @@
+//! diagnostic
+//!
+//! Errors are shown alongside the line of source that caused them:
+//!
+//! ```text
+//! ╭ length error
+//! │   1 2 + 1 2 3
+//! │       ^
+//! ╰ shapes [2] and [3] don't match
+//! ```
+
+use crate::prelude::{ErrorKind, Span};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// An error, ready to be shown to a person
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    headline: String,
+    span: Option<Span>,
+    note: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic from an error.
+    pub fn new(error: &anyhow::Error) -> Diagnostic {
+        match error.downcast_ref::<ErrorKind>() {
+            Some(kind) => Diagnostic {
+                headline: kind.headline(),
+                span: kind.span(),
+                note: kind.note(),
+            },
+            None => Diagnostic {
+                headline: error.to_string(),
+                span: None,
+                note: None,
+            },
+        }
+    }
+
+    /// Get the headline of the diagnostic.
+    pub fn headline(&self) -> &str {
+        &self.headline
+    }
+
+    /// Get the span the diagnostic points to.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Get the note at the end of the diagnostic.
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Render the diagnostic, quoting the offending line of the source it came from.
+    pub fn render(&self, source: &str) -> String {
+        let mut lines = vec![self.headline.clone()];
+
+        if let Some((line, underline)) = self.span.and_then(|span| excerpt(source, span)) {
+            lines.push(format!("  {}", line));
+            lines.push(format!("  {}", underline));
+        }
+
+        if let Some(note) = &self.note {
+            lines.push(note.clone());
+        }
+
+        let last = lines.len() - 1;
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let margin = match i {
+                    0 if last == 0 => "╶",
+                    0 => "╭",
+                    i if i == last => "╰",
+                    _ => "│",
+                };
+                format!("{} {}", margin, line).trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Render an error against the source it came from.
+pub fn render(source: &str, error: &anyhow::Error) -> String {
+    Diagnostic::new(error).render(source)
+}
+
+// Get the line a span starts on, and a line underlining the span.
+fn excerpt(source: &str, span: Span) -> Option<(String, String)> {
+    let line = source.lines().nth(span.line().checked_sub(1)?)?;
+    let graphemes: Vec<&str> = line.graphemes(true).collect();
+
+    // spans running past the end of the line are underlined up to the end of it
+    let start = span.start().column().min(graphemes.len());
+    let end = if span.end().line() == span.start().line() {
+        span.end().column().min(graphemes.len())
+    } else {
+        graphemes.len()
+    };
+
+    let indent: String = graphemes[..start]
+        .iter()
+        .map(|&grapheme| if grapheme == "\t" { "\t" } else { " " })
+        .collect();
+    let underline = "^".repeat(end.saturating_sub(start).max(1));
+
+    Some((line.to_string(), indent + &underline))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::{parse, Interpreter};
+
+    #[test]
+    fn diagnostic1() {
+        let source = "1 2 + 1 2 3";
+        let error = Interpreter::new().run(source).err().unwrap();
+        assert_eq!(
+            render(source, &error),
+            "╭ length error\n\
+             │   1 2 + 1 2 3\n\
+             │       ^\n\
+             ╰ shapes [2] and [3] don't match"
+        );
+    }
+
+    #[test]
+    fn diagnostic2() {
+        let source = "x ← 3\n{⍵ + ⍵} ⍬";
+        let error = Interpreter::new().run(source).err().unwrap();
+        assert_eq!(
+            render(source, &error),
+            "╭ missing argument\n\
+             │   {⍵ + ⍵} ⍬\n\
+             │        ^\n\
+             ╰ expected right argument, got nothing"
+        );
+    }
+
+    #[test]
+    fn diagnostic3() {
+        let source = "x ← 1\nnope + x";
+        let error = Interpreter::new().run(source).err().unwrap();
+        assert_eq!(
+            render(source, &error),
+            "╭ undefined name\n\
+             │   nope + x\n\
+             │   ^^^^\n\
+             ╰ nope hasn't been assigned"
+        );
+    }
+
+    #[test]
+    fn diagnostic4() {
+        let source = "(1 + 2";
+        let error = parse(source).err().unwrap();
+        let diagnostic = Diagnostic::new(&error);
+        assert_eq!(diagnostic.headline(), "syntax error");
+        assert_eq!(diagnostic.note(), Some("unclosed '('"));
+        assert_eq!(
+            diagnostic.render(source),
+            "╭ syntax error\n\
+             │   (1 + 2\n\
+             │   ^\n\
+             ╰ unclosed '('"
+        );
+
+        let error = anyhow::anyhow!("something else");
+        assert_eq!(render(source, &error), "╶ something else");
+    }
+}
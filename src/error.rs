@@ -10,6 +10,124 @@ pub enum ErrorKind {
     InvalidUnicode { span: Span },
     #[error("Unterminated string starting at {span}")]
     UnterminatedString { span: Span },
-    #[error("Unknown escape code {code}")]
-    UnknownEscapeCode { code: String },
+    #[error("Unknown escape code {code} at {span}")]
+    UnknownEscapeCode { code: String, span: Span },
+    #[error("{why}\nat {span}")]
+    RuntimeError { why: anyhow::Error, span: Span },
+    #[error("Undefined name {name}")]
+    UndefinedName { name: String },
+    #[error("Missing argument: expected {side} argument, got nothing")]
+    MissingArgument { side: String },
+    #[error("Not a dyad: '{op}' requires a dyad")]
+    NotADyad { op: String },
+    #[error("Domain error: {why}")]
+    DomainError { why: String },
+    #[error("Length error: {why}")]
+    LengthError { why: String },
+    #[error("Rank error: {why}")]
+    RankError { why: String },
+    #[error("Index out of bounds: wanted {wanted}, but array is length {length}")]
+    IndexOutOfBounds { wanted: i64, length: usize },
+    #[error("Rank mismatch: array is rank {rank}, but picked {picked} indices")]
+    RankMismatch { rank: usize, picked: usize },
+    #[error("Unsupported: {what}")]
+    Unsupported { what: String },
+    #[error("Recursion too deep: quotes can only be {depth} calls deep")]
+    RecursionTooDeep { depth: usize },
+}
+
+impl ErrorKind {
+    /// A short description of the error, for the first line of a diagnostic.
+    pub fn headline(&self) -> String {
+        match self {
+            ErrorKind::SyntaxError { .. } => String::from("syntax error"),
+            ErrorKind::InvalidUnicode { .. } => String::from("invalid unicode"),
+            ErrorKind::UnterminatedString { .. } => String::from("unterminated string"),
+            ErrorKind::UnknownEscapeCode { .. } => String::from("unknown escape code"),
+            ErrorKind::RuntimeError { why, .. } => match why.downcast_ref::<ErrorKind>() {
+                Some(kind) => kind.headline(),
+                None => why.to_string(),
+            },
+            ErrorKind::UndefinedName { .. } => String::from("undefined name"),
+            ErrorKind::MissingArgument { .. } => String::from("missing argument"),
+            ErrorKind::NotADyad { .. } => String::from("not a dyad"),
+            ErrorKind::DomainError { .. } => String::from("domain error"),
+            ErrorKind::LengthError { .. } => String::from("length error"),
+            ErrorKind::RankError { .. } => String::from("rank error"),
+            ErrorKind::IndexOutOfBounds { .. } => String::from("index out of bounds"),
+            ErrorKind::RankMismatch { .. } => String::from("rank mismatch"),
+            ErrorKind::Unsupported { .. } => String::from("unsupported"),
+            ErrorKind::RecursionTooDeep { .. } => String::from("recursion too deep"),
+        }
+    }
+
+    /// More detail about the error, if there is any.
+    pub fn note(&self) -> Option<String> {
+        match self {
+            ErrorKind::SyntaxError { why, .. } => Some(why.to_string()),
+            ErrorKind::InvalidUnicode { .. } => None,
+            ErrorKind::UnterminatedString { .. } => Some(String::from("strings end with '")),
+            ErrorKind::UnknownEscapeCode { code, .. } => {
+                Some(format!("\\{} isn't an escape code, only \\' is", code))
+            }
+            ErrorKind::RuntimeError { why, .. } => {
+                why.downcast_ref::<ErrorKind>().and_then(ErrorKind::note)
+            }
+            ErrorKind::UndefinedName { name } => Some(format!("{} hasn't been assigned", name)),
+            ErrorKind::MissingArgument { side } => {
+                Some(format!("expected {} argument, got nothing", side))
+            }
+            ErrorKind::NotADyad { op } => Some(format!("'{}' requires a dyad", op)),
+            ErrorKind::DomainError { why }
+            | ErrorKind::LengthError { why }
+            | ErrorKind::RankError { why } => Some(why.clone()),
+            ErrorKind::IndexOutOfBounds { wanted, length } => Some(format!(
+                "wanted {}, but array is length {}",
+                wanted.to_string().replace('-', "¯"),
+                length
+            )),
+            ErrorKind::RankMismatch { rank, picked } => Some(format!(
+                "array is rank {}, but picked {} order element",
+                rank,
+                ordinal(*picked)
+            )),
+            ErrorKind::Unsupported { what } => Some(format!("{} isn't supported yet", what)),
+            ErrorKind::RecursionTooDeep { depth } => {
+                Some(format!("quotes can only be {} calls deep", depth))
+            }
+        }
+    }
+
+    /// Where the error happened, if it's known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ErrorKind::SyntaxError { span, .. }
+            | ErrorKind::InvalidUnicode { span }
+            | ErrorKind::UnterminatedString { span }
+            | ErrorKind::UnknownEscapeCode { span, .. }
+            | ErrorKind::RuntimeError { span, .. } => Some(*span),
+            ErrorKind::UndefinedName { .. }
+            | ErrorKind::MissingArgument { .. }
+            | ErrorKind::NotADyad { .. }
+            | ErrorKind::DomainError { .. }
+            | ErrorKind::LengthError { .. }
+            | ErrorKind::RankError { .. }
+            | ErrorKind::IndexOutOfBounds { .. }
+            | ErrorKind::RankMismatch { .. }
+            | ErrorKind::Unsupported { .. }
+            | ErrorKind::RecursionTooDeep { .. } => None,
+        }
+    }
+}
+
+// Write a number like 1st, 2nd, 3rd.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
 }
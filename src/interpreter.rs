@@ -0,0 +1,1370 @@
+//! interpreter
+
+use crate::prelude::{
+    anyhow, parse_with, Array, Atom, Element, ErrorKind, Expression, FileId, Result, Span,
+    Statement, SymbolTable, Token, TokenKind,
+};
+use crate::scalar;
+
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The result of evaluating an expression
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Array(Array),
+    Function(Function),
+}
+
+/// Something that can be called with arguments
+#[derive(Debug, PartialEq, Clone)]
+pub enum Function {
+    /// A builtin glyph, like + or ⍴
+    Builtin(Token),
+    /// A quote, holding its Expression::Lambda
+    Lambda(Expression),
+    /// A function spread or scanned through its argument
+    Spread { verb: Box<Function>, slash: Token },
+    /// A builtin glyph along an axis, like ,[0.5]
+    Axis { glyph: Token, axis: Element },
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Array(array) => write!(f, "{}", array),
+            Value::Function(function) => write!(f, "{}", function),
+        }
+    }
+}
+
+impl std::fmt::Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Function::Builtin(glyph) => write!(f, "{}", glyph.lexeme()),
+            Function::Lambda(lambda) => write!(f, "{}", lambda),
+            Function::Spread { verb, slash } => write!(f, "{}{}", verb, slash.lexeme()),
+            Function::Axis { glyph, axis } => write!(f, "{}[{}]", glyph.lexeme(), axis),
+        }
+    }
+}
+
+// How many quotes can be called inside each other before giving up. This fits in the 8MB stack of a main thread,
+// even without optimizations.
+const MAX_DEPTH: usize = 256;
+
+// The arguments of the quote currently being evaluated.
+struct Frame {
+    alpha: Option<Value>,
+    omega: Option<Value>,
+}
+
+/// A tree-walking interpreter
+///
+/// Bindings made with ← are global and persist between calls to run, so quotes are evaluated with whatever their
+/// names refer to at the time they are called.
+#[derive(Default)]
+pub struct Interpreter {
+    // every source that has been run, indexed by FileId
+    sources: Vec<String>,
+    globals: HashMap<String, Value>,
+    frames: Vec<Frame>,
+    symbols: SymbolTable,
+    system: System,
+}
+
+// Settings that can be read and changed from the language through system variables like ⎕IO.
+struct System {
+    // what the first index counts from, 1 like the README by default or 0 like Rust
+    index_origin: i64,
+}
+
+impl Default for System {
+    fn default() -> Self {
+        System { index_origin: 1 }
+    }
+}
+
+impl Interpreter {
+    /// Create an interpreter with no bindings.
+    pub fn new() -> Interpreter {
+        Interpreter::default()
+    }
+
+    /// Get the value bound to a name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.globals.get(name)
+    }
+
+    /// Get the index origin, which is what indices count from.
+    pub fn index_origin(&self) -> i64 {
+        self.system.index_origin
+    }
+
+    /// Set the index origin, which can be 0 or 1.
+    pub fn set_index_origin(&mut self, origin: i64) -> Result<()> {
+        if origin != 0 && origin != 1 {
+            return Err(domain_error(format!(
+                "the index origin can be 0 or 1, not {}",
+                origin
+            )));
+        }
+
+        self.system.index_origin = origin;
+        Ok(())
+    }
+
+    /// Get a source that has been run, so errors can be shown against it.
+    pub fn source(&self, file: FileId) -> Option<&str> {
+        self.sources.get(file.index()).map(String::as_str)
+    }
+
+    /// Parse and execute a source, returning the values of any statements that aren't assignments.
+    ///
+    /// Each source gets its own FileId, so errors in quotes can point back to where they were written.
+    pub fn run(&mut self, source: &str) -> Result<Vec<Value>> {
+        let file = FileId::new(self.sources.len());
+        self.sources.push(source.to_string());
+
+        let statements = parse_with(source, file, &mut self.symbols)?;
+
+        let mut values = Vec::new();
+        for statement in statements.iter() {
+            if let Some(value) = self.execute(statement)? {
+                values.push(value);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Execute a statement, returning its value if it isn't an assignment.
+    pub fn execute(&mut self, statement: &Statement) -> Result<Option<Value>> {
+        match statement {
+            Statement::Assign { name, expression } if name.lexeme().starts_with('⎕') => {
+                let value = self.evaluate(expression)?;
+                self.set_system(name, value)?;
+                Ok(None)
+            }
+
+            Statement::Assign { name, expression } => {
+                let value = self.evaluate(expression)?;
+                if let Value::Function(_) = value {
+                    self.symbols.define_function(name.lexeme());
+                } else {
+                    self.symbols.define_value(name.lexeme());
+                }
+                self.globals.insert(name.lexeme().to_string(), value);
+                Ok(None)
+            }
+
+            Statement::AssignQuote { name, .. } => {
+                Err(unsupported("quote assignment", name.span()))
+            }
+
+            Statement::Expression(expression) => self.evaluate(expression).map(Some),
+        }
+    }
+
+    // Change a system variable.
+    fn set_system(&mut self, name: &Token, value: Value) -> Result<()> {
+        match name.lexeme() {
+            "⎕IO" => {
+                let origin = element(value).map_err(at(name.span()))?;
+                let origin = origin.as_integer().ok_or_else(|| {
+                    at(name.span())(domain_error(format!(
+                        "the index origin can be 0 or 1, not {}",
+                        origin
+                    )))
+                })?;
+                self.set_index_origin(origin).map_err(at(name.span()))
+            }
+            lexeme => Err(unsupported(
+                format!("the system variable {}", lexeme),
+                name.span(),
+            )),
+        }
+    }
+
+    /// Evaluate an expression.
+    pub fn evaluate(&mut self, expression: &Expression) -> Result<Value> {
+        match expression {
+            Expression::Ident { name } => self.ident(name),
+
+            Expression::List { value } => list(value).map(Value::Array),
+
+            Expression::Strand { items } => {
+                // strands are evaluated right to left like everything else
+                let mut data = items
+                    .iter()
+                    .rev()
+                    .map(|item| {
+                        let value = self.evaluate(item)?;
+                        element(value).map_err(at(item.span()))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                data.reverse();
+                Ok(Value::Array(Array::vector(data)))
+            }
+
+            Expression::MonadCall { op, lhs } => {
+                let function = self.function(op)?;
+
+                // calling a quote with ⍬ calls it with no argument at all
+                let omega = match (&function, &**lhs) {
+                    (Function::Lambda(_), Expression::Ident { name }) if name.lexeme() == "⍬" => {
+                        None
+                    }
+                    _ => Some(self.evaluate(lhs)?),
+                };
+
+                self.apply(&function, None, omega, op.span())
+            }
+
+            Expression::DyadCall { rhs, op, lhs } => {
+                let omega = self.evaluate(lhs)?;
+                let function = self.function(op)?;
+                let alpha = self.evaluate(rhs)?;
+                self.apply(&function, Some(alpha), Some(omega), op.span())
+            }
+
+            Expression::Spread { verb, slash } => Ok(Value::Function(Function::Spread {
+                verb: Box::new(self.function(verb)?),
+                slash: slash.clone(),
+            })),
+
+            Expression::Lambda { .. } => Ok(Value::Function(Function::Lambda(expression.clone()))),
+
+            Expression::Axis { verb, axis, .. } => {
+                let glyph = match &**verb {
+                    Expression::Ident { name } => name.clone(),
+                    _ => unreachable!("only builtins take an axis"),
+                };
+
+                let value = self.evaluate(axis)?;
+                let axis = match element(value).map_err(at(axis.span()))? {
+                    axis if axis.is_number() => axis,
+                    axis => {
+                        return Err(at(glyph.span())(domain_error(format!(
+                            "an axis must be a number, not {}",
+                            axis
+                        ))))
+                    }
+                };
+                Ok(Value::Function(Function::Axis { glyph, axis }))
+            }
+
+            Expression::Index {
+                array: target,
+                left_bracket,
+                indices,
+                ..
+            } => {
+                let mut selected = indices
+                    .iter()
+                    .rev()
+                    .map(|index| match index {
+                        Some(index) => {
+                            let value = self.evaluate(index)?;
+                            array(value).map(Some).map_err(at(index.span()))
+                        }
+                        None => Ok(None),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                selected.reverse();
+
+                let value = self.evaluate(target)?;
+                array(value)
+                    .and_then(|array| array.index(&selected, self.system.index_origin))
+                    .map(Value::Array)
+                    .map_err(at(left_bracket.span()))
+            }
+
+            Expression::Quote { colon, .. } | Expression::Call { colon, .. } => {
+                Err(unsupported("':' quotes", colon.span()))
+            }
+        }
+    }
+
+    // Evaluate an expression that must be a function.
+    fn function(&mut self, expression: &Expression) -> Result<Function> {
+        match self.evaluate(expression)? {
+            Value::Function(function) => Ok(function),
+            Value::Array(_) => Err(at(expression.span())(domain_error(
+                "expected a function, got an array",
+            ))),
+        }
+    }
+
+    // Look up a name or glyph.
+    fn ident(&mut self, name: &Token) -> Result<Value> {
+        let argument = |frame: Option<&Frame>, side: &str| {
+            let value = match frame {
+                Some(frame) if side == "left" => frame.alpha.clone(),
+                Some(frame) => frame.omega.clone(),
+                None => None,
+            };
+
+            value.ok_or_else(|| {
+                at(name.span())(anyhow!(ErrorKind::MissingArgument {
+                    side: side.to_string(),
+                }))
+            })
+        };
+
+        match (name.kind(), name.lexeme()) {
+            (TokenKind::Builtin, "⍺") => argument(self.frames.last(), "left"),
+            (TokenKind::Builtin, "⍵") => argument(self.frames.last(), "right"),
+            (TokenKind::Builtin, "⍬") => Ok(Value::Array(Array::vector(Vec::new()))),
+            (TokenKind::Builtin, _) => Ok(Value::Function(Function::Builtin(name.clone()))),
+            (_, "⎕IO") => Ok(Value::Array(Array::from_element(Element::Int(
+                self.system.index_origin,
+            )))),
+            (_, lexeme) => self.globals.get(lexeme).cloned().ok_or_else(|| {
+                at(name.span())(anyhow!(ErrorKind::UndefinedName {
+                    name: lexeme.to_string(),
+                }))
+            }),
+        }
+    }
+
+    /// Call a function with its arguments.
+    pub fn apply(
+        &mut self,
+        function: &Function,
+        alpha: Option<Value>,
+        omega: Option<Value>,
+        span: Span,
+    ) -> Result<Value> {
+        match function {
+            Function::Builtin(glyph) => {
+                let omega = omega.ok_or_else(|| {
+                    at(span)(anyhow!(ErrorKind::MissingArgument {
+                        side: String::from("right"),
+                    }))
+                })?;
+                let omega = array(omega).map_err(at(span))?;
+
+                let result = match alpha {
+                    Some(alpha) => {
+                        let alpha = array(alpha).map_err(at(span))?;
+                        builtin_dyad(glyph.lexeme(), &alpha, &omega, self.system.index_origin)
+                    }
+                    None => builtin_monad(glyph.lexeme(), &omega, self.system.index_origin),
+                };
+
+                result.map(Value::Array).map_err(at(span))
+            }
+
+            Function::Lambda(lambda) => {
+                let body = match lambda {
+                    Expression::Lambda { expression, .. } => expression,
+                    _ => unreachable!("not a lambda"),
+                };
+
+                if self.frames.len() >= MAX_DEPTH {
+                    return Err(at(span)(anyhow!(ErrorKind::RecursionTooDeep {
+                        depth: MAX_DEPTH
+                    })));
+                }
+
+                self.frames.push(Frame { alpha, omega });
+                let result = self.evaluate(body);
+                self.frames.pop();
+                result
+            }
+
+            Function::Axis { glyph, axis } => {
+                let (alpha, omega) = match (alpha, omega) {
+                    (Some(alpha), Some(omega)) => (alpha, omega),
+                    _ => return Err(unsupported(format!("monadic {}", function), span)),
+                };
+                let alpha = array(alpha).map_err(at(span))?;
+                let omega = array(omega).map_err(at(span))?;
+
+                builtin_axis(
+                    glyph.lexeme(),
+                    &alpha,
+                    &omega,
+                    axis,
+                    self.system.index_origin,
+                )
+                .map(Value::Array)
+                .map_err(at(span))
+            }
+
+            Function::Spread { verb, slash } => {
+                if alpha.is_some() {
+                    return Err(unsupported(format!("dyadic {}", slash.lexeme()), span));
+                }
+
+                let omega = omega.ok_or_else(|| {
+                    at(span)(anyhow!(ErrorKind::MissingArgument {
+                        side: String::from("right"),
+                    }))
+                })?;
+                let omega = array(omega).map_err(at(span))?;
+
+                self.spread(verb, slash, &omega)
+            }
+        }
+    }
+
+    // Put a dyad between each element of an array, evaluating from right to left. / and ⌿ reduce, \ and ⍀ give
+    // each of the reductions along the way.
+    fn spread(&mut self, verb: &Function, slash: &Token, omega: &Array) -> Result<Value> {
+        if let Function::Lambda(lambda) = verb {
+            if !uses_alpha(lambda) {
+                return Err(at(slash.span())(anyhow!(ErrorKind::NotADyad {
+                    op: slash.lexeme().to_string(),
+                })));
+            }
+        }
+
+        // / and \ go along the last axis, ⌿ and ⍀ along the first
+        let axis = match slash.lexeme() {
+            "/" | "\\" => omega.shape().len().saturating_sub(1),
+            _ => 0,
+        };
+        let glyph = match verb {
+            Function::Builtin(glyph) => glyph.lexeme(),
+            _ => "",
+        };
+
+        let span = slash.span();
+        let f = |next: &Element, acc: &Element| {
+            let result = self.apply(
+                verb,
+                Some(Value::Array(Array::from_element(next.clone()))),
+                Some(Value::Array(Array::from_element(acc.clone()))),
+                span,
+            )?;
+            element(result)
+        };
+
+        let result = match slash.lexeme() {
+            "/" | "⌿" => omega.fold_axis(axis, identity(glyph).as_ref(), f),
+            _ => omega.scan_axis(axis, is_associative(glyph), f),
+        };
+
+        result.map(Value::Array).map_err(at(span))
+    }
+}
+
+// Builtin functions with only a right argument. Indices count from origin.
+fn builtin_monad(glyph: &str, omega: &Array, origin: i64) -> Result<Array> {
+    match glyph {
+        "+" => scalar::monad(scalar::conjugate, omega),
+        "-" => scalar::monad(scalar::negate, omega),
+        "×" => scalar::monad(scalar::signum, omega),
+        "*" => scalar::monad(scalar::exponential, omega),
+        "÷" => scalar::monad(scalar::reciprocal, omega),
+        "|" => scalar::monad(scalar::magnitude, omega),
+        "⌈" => scalar::monad(scalar::ceiling, omega),
+        "⌊" => scalar::monad(scalar::floor, omega),
+        "~" => scalar::monad(scalar::not, omega),
+        "⍴" => Ok(Array::vector(
+            omega
+                .shape()
+                .iter()
+                .map(|&length| Element::Int(length as i64))
+                .collect(),
+        )),
+        "," => Ok(omega.ravel()),
+        "⍸" => omega.positions(origin),
+        "⍋" => omega.grade(false, origin),
+        "∪" => Ok(omega.unique()),
+        "⍒" => omega.grade(true, origin),
+        "⍳" if omega.is_scalar() => Ok(Array::range(lengths(omega)?[0], origin)),
+        "⍳" => Ok(Array::indices(&lengths(omega)?, origin)),
+        "⌽" => Ok(omega.reverse(omega.shape().len().saturating_sub(1))),
+        "⊖" => Ok(omega.reverse(0)),
+        "⍉" => {
+            let axes: Vec<usize> = (0..omega.shape().len()).rev().collect();
+            omega.transpose(&axes)
+        }
+        "⊢" | "⊣" => Ok(omega.clone()),
+        _ => Err(anyhow!(ErrorKind::Unsupported {
+            what: format!("monadic {}", glyph),
+        })),
+    }
+}
+
+// Builtin functions with both arguments. Indices count from origin.
+fn builtin_dyad(glyph: &str, alpha: &Array, omega: &Array, origin: i64) -> Result<Array> {
+    match glyph {
+        "+" => scalar::dyad(scalar::add, alpha, omega),
+        "-" => scalar::dyad(scalar::subtract, alpha, omega),
+        "×" => scalar::dyad(scalar::multiply, alpha, omega),
+        "*" => scalar::dyad(scalar::power, alpha, omega),
+        "÷" => scalar::dyad(scalar::divide, alpha, omega),
+        "|" => scalar::dyad(scalar::residue, alpha, omega),
+        "⌈" => scalar::dyad(scalar::maximum, alpha, omega),
+        "⌊" => scalar::dyad(scalar::minimum, alpha, omega),
+        "<" => scalar::dyad(scalar::less, alpha, omega),
+        "≤" => scalar::dyad(scalar::less_equal, alpha, omega),
+        "=" => scalar::dyad(scalar::equal, alpha, omega),
+        "≥" => scalar::dyad(scalar::greater_equal, alpha, omega),
+        ">" => scalar::dyad(scalar::greater, alpha, omega),
+        "≠" => scalar::dyad(scalar::not_equal, alpha, omega),
+        "∧" => scalar::dyad(scalar::and, alpha, omega),
+        "∨" => scalar::dyad(scalar::or, alpha, omega),
+        "⍴" => omega.reshape(&lengths(alpha)?),
+        "," => {
+            let rank = alpha.shape().len().max(omega.shape().len());
+            alpha.catenate(omega, rank.saturating_sub(1))
+        }
+        // the same as ⍪[⎕IO], so rows are stacked by laminating vectors or catenating them to matrices
+        "⍪" => alpha.catenate(omega, 0),
+        "⌷" => omega.index(&picks(alpha), origin),
+        "⍳" => alpha.index_of(omega, origin),
+        "∪" => alpha.union(omega),
+        "∩" => alpha.intersection(omega),
+        "∊" => Ok(alpha.member(omega)),
+        "~" => alpha.without(omega),
+        "⍋" => omega.grade_collated(&collation(alpha)?, false, origin),
+        "⍒" => omega.grade_collated(&collation(alpha)?, true, origin),
+        "⍉" => omega.transpose(&axes(alpha, origin)?),
+        "↑" => omega.take(&counts(alpha)?),
+        "↓" => omega.drop(&counts(alpha)?),
+        "⌽" => omega.rotate(alpha, omega.shape().len().saturating_sub(1)),
+        "⊖" => omega.rotate(alpha, 0),
+        "⊢" => Ok(omega.clone()),
+        "⊣" => Ok(alpha.clone()),
+        _ => Err(anyhow!(ErrorKind::Unsupported {
+            what: format!("dyadic {}", glyph),
+        })),
+    }
+}
+
+// Split the left argument of ⌷ into an index for each axis.
+fn picks(alpha: &Array) -> Vec<Option<Array>> {
+    alpha
+        .data()
+        .iter()
+        .map(|element| match element {
+            Element::Array(array) => Some(array.clone()),
+            element => Some(Array::from_element(element.clone())),
+        })
+        .collect()
+}
+
+// Builtin functions with both arguments along an axis, counting from origin. , and ⍪ join along the axis, or along a
+// new axis before it if the axis is fractional.
+fn builtin_axis(
+    glyph: &str,
+    alpha: &Array,
+    omega: &Array,
+    axis: &Element,
+    origin: i64,
+) -> Result<Array> {
+    match glyph {
+        "," | "⍪" => match axis.as_integer() {
+            Some(axis) => alpha.catenate(omega, axis_number(axis, origin)?),
+            None => {
+                let after = scalar::subtract(axis, &Element::Int(origin))?;
+                let before = scalar::ceiling(&after)?
+                    .as_integer()
+                    .and_then(|before| usize::try_from(before).ok())
+                    .ok_or_else(|| domain_error(format!("no axis to laminate at {}", axis)))?;
+                alpha.laminate(omega, before)
+            }
+        },
+        _ => Err(anyhow!(ErrorKind::Unsupported {
+            what: format!("{} with an axis", glyph),
+        })),
+    }
+}
+
+// Get the lengths of a shape from an array, which must be a scalar or vector of non-negative integers.
+fn lengths(array: &Array) -> Result<Vec<usize>> {
+    let lengths = integers(array, "a shape")?
+        .into_iter()
+        .map(|i| {
+            usize::try_from(i)
+                .map_err(|_| domain_error(format!("a shape must be non-negative, not {}", i)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Array::size(&lengths)?;
+    Ok(lengths)
+}
+
+// Get the counts for ↑ and ↓ from an array, which must be a scalar or vector of integers.
+fn counts(array: &Array) -> Result<Vec<i64>> {
+    let counts = integers(array, "counts")?;
+    let lengths: Vec<usize> = counts
+        .iter()
+        .map(|count| usize::try_from(count.unsigned_abs()).unwrap_or(usize::MAX))
+        .collect();
+    Array::size(&lengths)?;
+    Ok(counts)
+}
+
+// Get the integers in an array, which must be a scalar or vector of whole numbers. What says what they are for.
+fn integers(array: &Array, what: &str) -> Result<Vec<i64>> {
+    if array.shape().len() > 1 {
+        return Err(domain_error(format!("{} must be a vector", what)));
+    }
+
+    array
+        .data()
+        .iter()
+        .map(|element| {
+            element
+                .as_integer()
+                .ok_or_else(|| domain_error(format!("{} must be integers, not {}", what, element)))
+        })
+        .collect()
+}
+
+// Get a collation sequence from an array of strings, which is their characters in order.
+fn collation(array: &Array) -> Result<Vec<char>> {
+    let mut characters = Vec::new();
+    for element in array.data() {
+        match element {
+            Element::String(string) => characters.extend(string.chars()),
+            _ => {
+                return Err(domain_error(format!(
+                    "a collation sequence must be strings, not {}",
+                    element
+                )))
+            }
+        }
+    }
+
+    Ok(characters)
+}
+
+// Get axis numbers from an array, which must be a scalar or vector of integers counting from origin.
+fn axes(array: &Array, origin: i64) -> Result<Vec<usize>> {
+    integers(array, "axes")?
+        .into_iter()
+        .map(|axis| axis_number(axis, origin))
+        .collect()
+}
+
+// Turn an axis counting from origin into one counting from 0.
+fn axis_number(axis: i64, origin: i64) -> Result<usize> {
+    axis.checked_sub(origin)
+        .and_then(|axis| usize::try_from(axis).ok())
+        .ok_or_else(|| domain_error(format!("axes count from {}, not {}", origin, axis)))
+}
+
+// The identity element of a builtin dyad, which reducing an empty array gives.
+fn identity(glyph: &str) -> Option<Element> {
+    match glyph {
+        "+" | "-" | "|" | "∨" | "<" | ">" | "≠" => Some(Element::Int(0)),
+        "×" | "*" | "÷" | "∧" | "≤" | "=" | "≥" => Some(Element::Int(1)),
+        "⌈" => Some(Element::Float(f64::NEG_INFINITY)),
+        "⌊" => Some(Element::Float(f64::INFINITY)),
+        _ => None,
+    }
+}
+
+// Check if a builtin dyad is associative, so scanning with it can reuse each reduction for the next.
+fn is_associative(glyph: &str) -> bool {
+    matches!(glyph, "+" | "×" | "⌈" | "⌊" | "∧" | "∨")
+}
+
+// Attach a span to an error, unless it already has one.
+fn at(span: Span) -> impl FnOnce(anyhow::Error) -> anyhow::Error {
+    move |why| match why.downcast_ref::<ErrorKind>() {
+        Some(ErrorKind::RuntimeError { .. }) | Some(ErrorKind::SyntaxError { .. }) => why,
+        _ => anyhow!(ErrorKind::RuntimeError { why, span }),
+    }
+}
+
+fn domain_error(why: impl ToString) -> anyhow::Error {
+    anyhow!(ErrorKind::DomainError {
+        why: why.to_string(),
+    })
+}
+
+fn unsupported(what: impl ToString, span: Span) -> anyhow::Error {
+    at(span)(anyhow!(ErrorKind::Unsupported {
+        what: what.to_string(),
+    }))
+}
+
+// Get the array out of a value.
+fn array(value: Value) -> Result<Array> {
+    match value {
+        Value::Array(array) => Ok(array),
+        Value::Function(function) => Err(domain_error(format!(
+            "expected an array, got the function {}",
+            function
+        ))),
+    }
+}
+
+// Turn a value into a single element, enclosing it if it isn't a scalar.
+fn element(value: Value) -> Result<Element> {
+    let array = array(value)?;
+    Ok(array.relegate().unwrap_or(Element::Array(array)))
+}
+
+// Turn literal tokens into an array.
+fn list(tokens: &[Token]) -> Result<Array> {
+    let data = tokens
+        .iter()
+        .map(|token| match token.kind() {
+            TokenKind::Int(i) => Ok(Element::Int(i)),
+            TokenKind::BigInt => Ok(scalar::integer(big_int(token.lexeme()))),
+            TokenKind::Rational => {
+                let (numerator, denominator) = token.lexeme().split_once('r').unwrap();
+                Ok(scalar::rational(BigRational::new(
+                    big_int(numerator),
+                    big_int(denominator),
+                )))
+            }
+            TokenKind::Float(f) => Ok(Element::Float(f)),
+            TokenKind::Atom => Ok(Element::Atom(Atom::new(&token.lexeme()["∆".len()..]))),
+            TokenKind::Complex(re, im) => Ok(Element::Complex(Complex64::new(re, im))),
+            TokenKind::String => {
+                let lexeme = token.lexeme();
+                let string = &lexeme[1..lexeme.len() - 1];
+                Ok(Element::String(string.replace("\\'", "'")))
+            }
+            _ => Err(unsupported(
+                format!("the literal {}", token.lexeme()),
+                token.span(),
+            )),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if data.len() == 1 {
+        Ok(Array::from_element(data.into_iter().next().unwrap()))
+    } else {
+        Ok(Array::vector(data))
+    }
+}
+
+// Parse an integer the scanner has already checked.
+fn big_int(digits: &str) -> BigInt {
+    digits.replace('¯', "-").parse().unwrap()
+}
+
+// Check if a quote refers to its left argument, and so can be used as a dyad.
+fn uses_alpha(lambda: &Expression) -> bool {
+    match lambda {
+        Expression::Lambda { expression, .. } => refers_to_alpha(expression),
+        _ => false,
+    }
+}
+
+fn refers_to_alpha(expression: &Expression) -> bool {
+    match expression {
+        Expression::Ident { name } => name.kind() == TokenKind::Builtin && name.lexeme() == "⍺",
+        Expression::List { .. } => false,
+        Expression::Strand { items } => items.iter().any(refers_to_alpha),
+        Expression::MonadCall { op, lhs } => refers_to_alpha(op) || refers_to_alpha(lhs),
+        Expression::DyadCall { rhs, op, lhs } => {
+            refers_to_alpha(rhs) || refers_to_alpha(op) || refers_to_alpha(lhs)
+        }
+        Expression::Quote { expression, .. } | Expression::Call { expression, .. } => {
+            refers_to_alpha(expression)
+        }
+        Expression::Spread { verb, .. } => refers_to_alpha(verb),
+        Expression::Axis { axis, .. } => refers_to_alpha(axis),
+        Expression::Index { array, indices, .. } => {
+            refers_to_alpha(array) || indices.iter().flatten().any(refers_to_alpha)
+        }
+        // quotes inside quotes have their own ⍺
+        Expression::Lambda { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::array;
+    use crate::prelude::Diagnostic;
+
+    fn ints(data: &[i64]) -> Value {
+        Value::Array(array::ints(&[data.len()], data))
+    }
+
+    fn int(i: i64) -> Value {
+        Value::Array(Array::from_element(Element::Int(i)))
+    }
+
+    fn run(interpreter: &mut Interpreter, source: &str) -> Value {
+        interpreter.run(source).unwrap().pop().unwrap()
+    }
+
+    fn runtime_error(interpreter: &mut Interpreter, source: &str) -> ErrorKind {
+        let error = interpreter.run(source).err().unwrap();
+        match error.downcast::<ErrorKind>().unwrap() {
+            ErrorKind::RuntimeError { why, .. } => why.downcast::<ErrorKind>().unwrap(),
+            error => panic!("not a runtime error: {}", error),
+        }
+    }
+
+    #[test]
+    fn interpret1() {
+        let mut i = Interpreter::new();
+        i.run("x ← 1 2 3 4 5 ⋄ y ← 6 7 8 9 10").unwrap();
+        assert_eq!(run(&mut i, "x + y"), ints(&[7, 9, 11, 13, 15]));
+        assert_eq!(run(&mut i, "x × y"), ints(&[6, 14, 24, 36, 50]));
+        assert_eq!(run(&mut i, "⍴ x"), ints(&[5]));
+        assert_eq!(run(&mut i, "⍴⍴ x"), ints(&[1]));
+        assert_eq!(run(&mut i, "1 + 2 × 3"), int(7));
+        assert_eq!(run(&mut i, "-x"), ints(&[-1, -2, -3, -4, -5]));
+    }
+
+    #[test]
+    fn interpret2() {
+        let mut i = Interpreter::new();
+        i.run("x ← 32").unwrap();
+        assert_eq!(run(&mut i, "{x+x} ⍬"), int(64));
+
+        // quotes are lazy
+        i.run("twox ← {x + x}").unwrap();
+        assert_eq!(run(&mut i, "twox ⍬"), int(64));
+        i.run("x ← 8").unwrap();
+        assert_eq!(run(&mut i, "twox ⍬"), int(16));
+
+        i.run("double ← {⍵ + ⍵}").unwrap();
+        assert_eq!(run(&mut i, "double double x"), int(32));
+        match i.get("double") {
+            Some(Value::Function(double)) => assert_eq!(double.to_string(), "{⍵+⍵}"),
+            _ => panic!("double isn't a function"),
+        }
+    }
+
+    #[test]
+    fn interpret3() {
+        let mut i = Interpreter::new();
+        assert!(matches!(
+            runtime_error(&mut i, "{⍵ + ⍵} ⍬"),
+            ErrorKind::MissingArgument { .. }
+        ));
+        assert!(matches!(
+            runtime_error(&mut i, "nope + 1"),
+            ErrorKind::UndefinedName { .. }
+        ));
+        assert!(matches!(
+            runtime_error(&mut i, "1 2 + 1 2 3"),
+            ErrorKind::LengthError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret4() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "+/ 1 2 3 4 5"), int(15));
+        assert_eq!(run(&mut i, "-/ 1 2 3"), int(2));
+
+        i.run("f ← {⍺/ 2×⍵}").unwrap();
+        assert!(matches!(
+            runtime_error(&mut i, "{1+⍵} f 1 2 3 4 5"),
+            ErrorKind::NotADyad { .. }
+        ));
+        assert_eq!(run(&mut i, "{⍺+1+⍵} f 1 2 3 4 5"), int(34));
+
+        i.run("avg ← {(+/ ⍵) ÷ (⍴ ⍵)}").unwrap();
+        assert_eq!(run(&mut i, "avg 42 8 15 4 16 23"), ints(&[18]));
+    }
+
+    #[test]
+    fn interpret5() {
+        let mut i = Interpreter::new();
+        assert_eq!(
+            run(&mut i, "'Robert Dufresne' 1992"),
+            Value::Array(Array::vector(vec![
+                Element::String(String::from("Robert Dufresne")),
+                Element::Int(1992)
+            ]))
+        );
+        assert_eq!(
+            run(&mut i, "(1 2) 3"),
+            Value::Array(Array::vector(vec![
+                Element::Array(Array::vector(vec![Element::Int(1), Element::Int(2)])),
+                Element::Int(3)
+            ]))
+        );
+        assert_eq!(run(&mut i, "'a' = 'a' 'b'"), ints(&[1, 0]));
+    }
+
+    #[test]
+    fn interpret6() {
+        let mut i = Interpreter::new();
+        i.run("f ← {⍵ + nope}").unwrap();
+        let error = i.run("f 1").err().unwrap();
+        let span = match error.downcast_ref::<ErrorKind>() {
+            Some(ErrorKind::RuntimeError { span, .. }) => *span,
+            _ => panic!("not a runtime error"),
+        };
+        assert_eq!(i.source(span.file()), Some("f ← {⍵ + nope}"));
+        assert_eq!(span.start().column(), 9);
+    }
+
+    #[test]
+    fn interpret7() {
+        let mut i = Interpreter::new();
+        i.run("avg ← {(+/ ⍵) ÷ (⍴ ⍵)}").unwrap();
+        assert_eq!(
+            run(&mut i, "avg 42 8 15 4 15"),
+            Value::Array(Array::vector(vec![Element::Float(16.8)]))
+        );
+        assert_eq!(run(&mut i, "avg 42 8 15 4 16 23"), ints(&[18]));
+        assert_eq!(
+            run(&mut i, "1.5 + 2j1"),
+            Value::Array(Array::from_element(Element::Complex(Complex64::new(
+                3.5, 1.0
+            ))))
+        );
+    }
+
+    #[test]
+    fn interpret8() {
+        let mut i = Interpreter::new();
+        assert_eq!(
+            run(&mut i, "1r2 + 1r3"),
+            Value::Array(Array::from_element(Element::Rational(BigRational::new(
+                5.into(),
+                6.into()
+            ))))
+        );
+        assert_eq!(run(&mut i, "4r2"), int(2));
+        assert_eq!(
+            run(&mut i, "9223372036854775807 + 1"),
+            Value::Array(Array::from_element(Element::BigInt(
+                BigInt::from(i64::MAX) + 1
+            )))
+        );
+        assert_eq!(
+            run(
+                &mut i,
+                "57984023098753490873254798053247098 - 57984023098753490873254798053247097"
+            ),
+            int(1)
+        );
+    }
+
+    #[test]
+    fn interpret9() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "∆year = ∆year ∆month"), ints(&[1, 0]));
+        assert_eq!(run(&mut i, "∆year ≠ 'year'"), int(1));
+        assert_eq!(
+            run(&mut i, "∆name ∆year").to_string(),
+            "┌→──────────┐\n│∆name ∆year│\n└∆──────────┘"
+        );
+    }
+
+    #[test]
+    fn interpret10() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "⍴ 5"), ints(&[]));
+        assert_eq!(run(&mut i, "⍴ ⍴ 5"), ints(&[0]));
+        assert_eq!(run(&mut i, "⍴ , 5"), ints(&[1]));
+        assert_eq!(run(&mut i, "⍴ ⍴ 1 2 3"), ints(&[1]));
+        assert_eq!(run(&mut i, "+/ 1 2 3"), int(6));
+        assert_eq!(run(&mut i, "(, 1) + 2"), ints(&[3]));
+    }
+
+    #[test]
+    fn interpret11() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "+/ ⍬"), int(0));
+        assert_eq!(run(&mut i, "×/ ⍬"), int(1));
+        assert_eq!(run(&mut i, "-/ 1 2 3"), int(2));
+        assert_eq!(run(&mut i, "+⌿ 1 2 3"), int(6));
+        assert_eq!(run(&mut i, "+/ 5"), int(5));
+        assert_eq!(run(&mut i, "{⍺ + ⍵}/ 1 2 3 4"), int(10));
+        assert!(matches!(
+            runtime_error(&mut i, "{⍺ + ⍵}/ ⍬"),
+            ErrorKind::DomainError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret12() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "+\\ 1 2 3 4"), ints(&[1, 3, 6, 10]));
+        assert_eq!(run(&mut i, "-\\ 1 2 3 4"), ints(&[1, -1, 2, -2]));
+        assert_eq!(run(&mut i, "⌈⍀ 3 1 4 1 5"), ints(&[3, 3, 4, 4, 5]));
+        assert_eq!(run(&mut i, "{⍺ - ⍵}\\ 1 2 3"), ints(&[1, -1, 2]));
+        assert_eq!(run(&mut i, "+\\ ⍬"), ints(&[]));
+        assert!(matches!(
+            runtime_error(&mut i, "{⍵}\\ 1 2"),
+            ErrorKind::NotADyad { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret13() {
+        let mut i = Interpreter::new();
+        assert_eq!(
+            run(&mut i, "2 3 ⍴ 1 2"),
+            Value::Array(Array::from_parts(
+                vec![2, 3],
+                [1, 2, 1, 2, 1, 2]
+                    .iter()
+                    .copied()
+                    .map(Element::Int)
+                    .collect()
+            ))
+        );
+        assert_eq!(run(&mut i, "4 ⍴ 7"), ints(&[7, 7, 7, 7]));
+        assert_eq!(run(&mut i, "3 ⍴ ⍬"), ints(&[0, 0, 0]));
+        assert_eq!(run(&mut i, "⍴ ⍴ ⍬ ⍴ 5"), ints(&[0]));
+        assert!(matches!(
+            runtime_error(&mut i, "¯1 ⍴ 5"),
+            ErrorKind::DomainError { .. }
+        ));
+        assert!(matches!(
+            runtime_error(&mut i, "1.5 ⍴ 5"),
+            ErrorKind::DomainError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret14() {
+        let mut i = Interpreter::new();
+        i.run("x ← 1 2 3 4 5\ny ← 6 7 8 9 10\nz ← 11 12 13 14 15")
+            .unwrap();
+        assert_eq!(run(&mut i, "x,y,z"), ints(&(1..=15).collect::<Vec<_>>()));
+        assert_eq!(run(&mut i, "x⍪y"), ints(&(1..=10).collect::<Vec<_>>()));
+        assert_eq!(run(&mut i, "x⍪y"), run(&mut i, "x⍪[1]y"));
+        assert_eq!(run(&mut i, "1⍪2 3"), ints(&[1, 2, 3]));
+        assert_eq!(run(&mut i, "⍴ x,[0.5]y"), ints(&[2, 5]));
+        assert_eq!(run(&mut i, "⍴ x,[1.5]y"), ints(&[5, 2]));
+        assert_eq!(run(&mut i, "⍴ (x,[0.5]y)⍪z"), ints(&[3, 5]));
+        assert_eq!(run(&mut i, "⍴ (x,[0.5]y),[1]z"), ints(&[3, 5]));
+        assert_eq!(run(&mut i, "⍴ (x,[0.5]y)⍪(x,[0.5]y)"), ints(&[4, 5]));
+        assert_eq!(run(&mut i, "⍴ (x,[0.5]y),0"), ints(&[2, 6]));
+        assert_eq!(run(&mut i, "1,2"), ints(&[1, 2]));
+        assert!(matches!(
+            runtime_error(&mut i, "x,[2.5]y"),
+            ErrorKind::RankError { .. }
+        ));
+        assert!(matches!(
+            runtime_error(&mut i, "x⍴[1]y"),
+            ErrorKind::Unsupported { .. }
+        ));
+
+        i.run("⎕IO ← 0").unwrap();
+        assert_eq!(run(&mut i, "⍴ x,[¯0.5]y"), ints(&[2, 5]));
+        assert_eq!(run(&mut i, "⍴ x,[0.5]y"), ints(&[5, 2]));
+        i.run("⎕IO ← 1").unwrap();
+
+        let error = i.run("(x,[0.5]y),1 2 3").err().unwrap();
+        let span = match error.downcast_ref::<ErrorKind>() {
+            Some(ErrorKind::RuntimeError { why, span }) => {
+                assert!(matches!(
+                    why.downcast_ref::<ErrorKind>(),
+                    Some(ErrorKind::LengthError { .. })
+                ));
+                *span
+            }
+            _ => panic!("not a runtime error"),
+        };
+        assert_eq!(span.start().column(), 10);
+    }
+
+    #[test]
+    fn interpret15() {
+        let mut i = Interpreter::new();
+        i.run("a ← 3 5 ⍴ ⍳ 15").unwrap();
+        assert_eq!(run(&mut i, "1 ⌷ 1 2 3 4"), int(1));
+        assert_eq!(run(&mut i, "1 ⌷ ⍴ a"), int(3));
+        assert_eq!(run(&mut i, "2 ⌷ a"), ints(&[6, 7, 8, 9, 10]));
+        assert_eq!(run(&mut i, "2 4 ⌷ a"), int(9));
+        assert_eq!(run(&mut i, "(1 3) 2 ⌷ a"), ints(&[2, 12]));
+        assert_eq!(run(&mut i, "a[2;4]"), int(9));
+        assert_eq!(run(&mut i, "a[;1]"), ints(&[1, 6, 11]));
+        assert_eq!(run(&mut i, "a[3 1;5]"), ints(&[15, 5]));
+        assert_eq!(run(&mut i, "(- a)[1;1]"), int(-1));
+        assert_eq!(run(&mut i, "10 20 30[2]"), int(20));
+
+        let error = i.run("8 ⌷ 1 2 3 4").err().unwrap();
+        assert_eq!(
+            crate::diagnostic::render("8 ⌷ 1 2 3 4", &error),
+            "╭ index out of bounds\n\
+             │   8 ⌷ 1 2 3 4\n\
+             │     ^\n\
+             ╰ wanted 8, but array is length 4"
+        );
+
+        let error = i.run("1 1 ⌷ 1 2 3 4").err().unwrap();
+        assert_eq!(
+            Diagnostic::new(&error).note(),
+            Some("array is rank 1, but picked 2nd order element")
+        );
+        assert!(matches!(
+            runtime_error(&mut i, "a[4;1]"),
+            ErrorKind::IndexOutOfBounds {
+                wanted: 4,
+                length: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn interpret16() {
+        let mut i = Interpreter::new();
+        i.run("a ← 2 3 ⍴ 1 2 3 4 5 6").unwrap();
+        assert_eq!(run(&mut i, "⎕IO"), int(1));
+        assert_eq!(run(&mut i, "1 ⌷ 10 20 30"), int(10));
+        assert_eq!(run(&mut i, "⍸ 0 1 0 2"), ints(&[2, 4, 4]));
+
+        i.run("⎕IO ← 0").unwrap();
+        assert_eq!(i.index_origin(), 0);
+        assert_eq!(run(&mut i, "⎕IO"), int(0));
+        assert_eq!(run(&mut i, "1 ⌷ 10 20 30"), int(20));
+        assert_eq!(run(&mut i, "a[1;0]"), int(4));
+        assert_eq!(run(&mut i, "⍸ 0 1 0 2"), ints(&[1, 3, 3]));
+        assert!(matches!(
+            runtime_error(&mut i, "3 ⌷ 10 20 30"),
+            ErrorKind::IndexOutOfBounds {
+                wanted: 3,
+                length: 3
+            }
+        ));
+
+        assert!(matches!(
+            runtime_error(&mut i, "⎕IO ← 2"),
+            ErrorKind::DomainError { .. }
+        ));
+        assert!(matches!(
+            runtime_error(&mut i, "⎕IO ← 0.5"),
+            ErrorKind::DomainError { .. }
+        ));
+        assert_eq!(i.index_origin(), 0);
+
+        i.run("⎕IO ← 1.0").unwrap();
+        assert_eq!(i.index_origin(), 1);
+        i.run("⎕IO ← 0r1").unwrap();
+        assert_eq!(i.index_origin(), 0);
+        assert!(matches!(
+            runtime_error(&mut i, "⎕XY ← 2"),
+            ErrorKind::Unsupported { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret17() {
+        let mut i = Interpreter::new();
+        i.run("a ← 2 3 ⍴ 1 2 3 4 5 6").unwrap();
+        assert_eq!(
+            run(&mut i, "⍉ a"),
+            Value::Array(Array::from_parts(
+                vec![3, 2],
+                [1, 4, 2, 5, 3, 6]
+                    .iter()
+                    .copied()
+                    .map(Element::Int)
+                    .collect()
+            ))
+        );
+        assert_eq!(run(&mut i, "⍉ 1 2 3"), ints(&[1, 2, 3]));
+        assert_eq!(run(&mut i, "1 1 ⍉ a"), ints(&[1, 5]));
+        assert_eq!(run(&mut i, "1 2 ⍉ a"), run(&mut i, "a"));
+
+        i.run("⎕IO ← 0").unwrap();
+        assert_eq!(run(&mut i, "1 0 ⍉ a"), run(&mut i, "⍉ a"));
+        assert!(matches!(
+            runtime_error(&mut i, "1 2 ⍉ a"),
+            ErrorKind::RankError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret18() {
+        let mut i = Interpreter::new();
+        i.run("a ← 2 3 ⍴ 1 2 3 4 5 6").unwrap();
+        assert_eq!(run(&mut i, "⌽ 1 2 3"), ints(&[3, 2, 1]));
+        assert_eq!(run(&mut i, "⊖ a"), run(&mut i, "2 3 ⍴ 4 5 6 1 2 3"));
+        assert_eq!(run(&mut i, "⌽ a"), run(&mut i, "2 3 ⍴ 3 2 1 6 5 4"));
+        assert_eq!(run(&mut i, "2 ⌽ 1 2 3 4"), ints(&[3, 4, 1, 2]));
+        assert_eq!(run(&mut i, "1 ¯1 ⌽ a"), run(&mut i, "2 3 ⍴ 2 3 1 6 4 5"));
+        assert_eq!(run(&mut i, "1 ⊖ a"), run(&mut i, "⊖ a"));
+        assert!(matches!(
+            runtime_error(&mut i, "1 2 3 ⌽ a"),
+            ErrorKind::LengthError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret19() {
+        let mut i = Interpreter::new();
+        i.run("a ← 2 3 ⍴ 1 2 3 4 5 6").unwrap();
+        assert_eq!(run(&mut i, "2 ↑ 1 2 3"), ints(&[1, 2]));
+        assert_eq!(run(&mut i, "¯5 ↑ 1 2 3"), ints(&[0, 0, 1, 2, 3]));
+        assert_eq!(run(&mut i, "1 ↓ 1 2 3"), ints(&[2, 3]));
+        assert_eq!(run(&mut i, "1 ↑ a"), run(&mut i, "1 3 ⍴ 1 2 3"));
+        assert_eq!(run(&mut i, "¯1 ¯2 ↑ a"), run(&mut i, "1 2 ⍴ 5 6"));
+        assert_eq!(run(&mut i, "0 1 ↓ a"), run(&mut i, "2 2 ⍴ 2 3 5 6"));
+        assert!(matches!(
+            runtime_error(&mut i, "1 1 1 ↑ a"),
+            ErrorKind::RankError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret20() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "⍳ 5"), ints(&[1, 2, 3, 4, 5]));
+        assert_eq!(run(&mut i, "⍳ 0"), ints(&[]));
+        assert_eq!(run(&mut i, "⍴ ⍳ 2 3"), ints(&[2, 3]));
+        assert_eq!(
+            run(&mut i, "(⍳ 2 3)[2;3]"),
+            Value::Array(Array::from_element(Element::Array(
+                array(ints(&[2, 3])).unwrap()
+            )))
+        );
+        assert_eq!(run(&mut i, "'a' 'b' 'c' ⍳ 'c' 'z'"), ints(&[3, 4]));
+        assert_eq!(run(&mut i, "(2 2 ⍴ 1 2 3 4) ⍳ 3 4"), int(2));
+
+        i.run("⎕IO ← 0").unwrap();
+        assert_eq!(run(&mut i, "⍳ 3"), ints(&[0, 1, 2]));
+        assert_eq!(run(&mut i, "5 6 7 ⍳ 7 5 9"), ints(&[2, 0, 3]));
+        assert!(matches!(
+            runtime_error(&mut i, "⍳ ¯1"),
+            ErrorKind::DomainError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret21() {
+        let mut i = Interpreter::new();
+        i.run("a ← 3 1 2 1").unwrap();
+        assert_eq!(run(&mut i, "⍋ a"), ints(&[2, 4, 3, 1]));
+        assert_eq!(run(&mut i, "⍒ a"), ints(&[1, 3, 2, 4]));
+        assert_eq!(run(&mut i, "a[⍋ a]"), ints(&[1, 1, 2, 3]));
+        assert_eq!(run(&mut i, "⍋ 3 2 ⍴ 3 1 1 2 1 1"), ints(&[3, 2, 1]));
+        assert_eq!(run(&mut i, "'cba' ⍋ 'abc' 'cab' 'bca'"), ints(&[2, 3, 1]));
+
+        i.run("⎕IO ← 0").unwrap();
+        assert_eq!(run(&mut i, "⍋ a"), ints(&[1, 3, 2, 0]));
+        assert!(matches!(
+            runtime_error(&mut i, "⍋ 5"),
+            ErrorKind::RankError { .. }
+        ));
+        assert!(matches!(
+            runtime_error(&mut i, "1 2 ⍋ 'a' 'b'"),
+            ErrorKind::DomainError { .. }
+        ));
+    }
+
+    #[test]
+    fn interpret22() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "∪ 1 2 1 3 2"), ints(&[1, 2, 3]));
+        assert_eq!(run(&mut i, "1 2 ∪ 2 3"), ints(&[1, 2, 3]));
+        assert_eq!(run(&mut i, "1 2 3 4 ∩ 4 2"), ints(&[2, 4]));
+        assert_eq!(run(&mut i, "1 2 3 4 ~ 4 2"), ints(&[1, 3]));
+        assert_eq!(run(&mut i, "2 5 ∊ 1 2 3"), ints(&[1, 0]));
+        assert_eq!(run(&mut i, "~ 1 0"), ints(&[0, 1]));
+        assert_eq!(
+            run(&mut i, "∪ 3 2 ⍴ 1 2 3 4 1 2"),
+            run(&mut i, "2 2 ⍴ 1 2 3 4")
+        );
+        assert_eq!(run(&mut i, "'a' 'b' 'c' ~ 'b'"), run(&mut i, "'a' 'c'"));
+        assert_eq!(run(&mut i, "(0.1 + 0.2) ∊ 0.3"), int(1));
+        assert!(matches!(
+            runtime_error(&mut i, "(⍳3) ∪ 2 2⍴1"),
+            ErrorKind::RankError { .. }
+        ));
+        assert_eq!(run(&mut i, "(⍳3) ⍳ 2 2⍴1"), run(&mut i, "2 2⍴1"));
+    }
+
+    #[test]
+    fn interpret23() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "2 * 3 10"), ints(&[8, 1024]));
+        assert_eq!(run(&mut i, "*/ 2 3 2"), int(512));
+        assert_eq!(run(&mut i, "*\\ 2 3 2"), ints(&[2, 8, 512]));
+        assert_eq!(run(&mut i, "*/ ⍬"), int(1));
+        assert_eq!(
+            run(&mut i, "* 0"),
+            Value::Array(Array::from_element(Element::Float(1.0)))
+        );
+    }
+
+    #[test]
+    fn interpret24() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "2.0 ⍴ 7"), ints(&[7, 7]));
+        assert_eq!(run(&mut i, "2.0 ↑ 1 2 3"), ints(&[1, 2]));
+        assert_eq!(run(&mut i, "¯1.0 ↓ 1 2 3"), ints(&[1, 2]));
+        assert_eq!(run(&mut i, "1.0 ⌽ 1 2 3"), ints(&[2, 3, 1]));
+        assert_eq!(
+            run(&mut i, "2.0 1 ⍉ 2 2 ⍴ 1 2 3 4"),
+            run(&mut i, "⍉ 2 2 ⍴ 1 2 3 4")
+        );
+        assert_eq!(run(&mut i, "1 ⌽ 5"), int(5));
+        for source in [
+            "2.5 ⍴ 7",
+            "0.5 ↑ 1 2",
+            "0.5 ⌽ 1 2",
+            "0.5 ⌽ 5",
+            "'a' ⊖ 5",
+            // too many elements to make, whether or not counting them overflows
+            "4294967296 4294967296 ⍴ 1",
+            "4294967296 4294967296 ↑ 1",
+            "¯9223372036854775808 ↑ 1 2",
+            "⍳ 4294967296 4294967296",
+            "⍳ 9223372036854775807",
+            "1e11 ⍴ 1",
+            "1e11 ↑ 1",
+            "⍳ 1e11",
+        ] {
+            assert!(
+                matches!(runtime_error(&mut i, source), ErrorKind::DomainError { .. }),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn interpret25() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "(0.1+0.2) = 0.3"), int(1));
+        assert_eq!(run(&mut i, "(0.1+0.2) ≠ 0.3"), int(0));
+        assert_eq!(run(&mut i, "((0.1+0.2) = 0.3) = (0.3 ∊ 0.1+0.2)"), int(1));
+        assert_eq!(run(&mut i, "1 2 ∪ 2 3 3"), ints(&[1, 2, 3, 3]));
+    }
+
+    #[test]
+    fn interpret26() {
+        let mut i = Interpreter::new();
+        assert_eq!(run(&mut i, "⍸ 2 0 1"), ints(&[1, 1, 3]));
+        for source in [
+            "⍸ 1e15",
+            "⍸ 3000000000",
+            "⍸ 9223372036854775807 9223372036854775807",
+        ] {
+            assert!(
+                matches!(runtime_error(&mut i, source), ErrorKind::DomainError { .. }),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn interpret27() {
+        // test threads have smaller stacks than the main thread a REPL runs on
+        let test = std::thread::Builder::new().stack_size(8 << 20).spawn(|| {
+            let mut i = Interpreter::new();
+            i.run("f ← {f ⍵}").unwrap();
+            assert!(matches!(
+                runtime_error(&mut i, "f 1"),
+                ErrorKind::RecursionTooDeep { .. }
+            ));
+
+            // the frames are unwound, so calling quotes still works afterwards
+            i.run("g ← {⍵ + 1}").unwrap();
+            assert_eq!(run(&mut i, "g g 1"), int(3));
+        });
+        test.unwrap().join().unwrap();
+    }
+}
@@ -7,14 +7,21 @@ extern crate thiserror;
 extern crate unicode_segmentation;
 
 mod array;
+mod diagnostic;
 mod error;
+mod interpreter;
 mod parser;
+mod render;
+mod scalar;
 mod scanner;
 
 pub mod prelude {
     pub use crate::anyhow::{anyhow, Context, Error, Result};
-    pub use crate::array::{Array, Element};
+    pub use crate::array::{Array, Atom, Element};
+    pub use crate::diagnostic::Diagnostic;
     pub use crate::error::ErrorKind;
-    pub use crate::parser::{parse, Expression, Statement};
-    pub use crate::scanner::{Scanner, Span, Token, TokenKind};
+    pub use crate::interpreter::{Function, Interpreter, Value};
+    pub use crate::parser::{parse, parse_with, Expression, Statement, SymbolTable};
+    pub use crate::render::Renderer;
+    pub use crate::scanner::{FileId, Location, Scanner, Span, Token, TokenKind};
 }
@@ -0,0 +1,118 @@
+use sponk::prelude::*;
+
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+
+const HISTORY_FILE: &str = ".sponk_history";
+const PROMPT: &str = "    ";
+const CONTINUATION_PROMPT: &str = "  … ";
+
+fn main() {
+    println!("sponk");
+    std::process::exit(match run() {
+        Ok(_) => 0,
+        Err(e) => {
+            println!("{}", e);
+            1
+        }
+    })
+}
+
+fn run() -> Result<()> {
+    let mut editor = Editor::<()>::new();
+    // there's no history the first time around
+    let _ = editor.load_history(HISTORY_FILE);
+
+    // save the history however the loop ends
+    let result = repl(&mut editor);
+    editor.save_history(HISTORY_FILE)?;
+    result
+}
+
+// Read and run lines until the end of input.
+fn repl(editor: &mut Editor<()>) -> Result<()> {
+    let mut interpreter = Interpreter::new();
+    let mut source = String::new();
+
+    loop {
+        let prompt = if source.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+
+        match editor.readline(prompt) {
+            Ok(line) => {
+                source.push_str(&line);
+                source.push('\n');
+
+                // keep reading until every brace and paren is closed
+                if is_unbalanced(&source) {
+                    continue;
+                }
+
+                editor.add_history_entry(source.trim_end());
+                match interpreter.run(&source) {
+                    Ok(values) => values.iter().for_each(|value| println!("{}", value)),
+                    Err(e) => {
+                        // errors in quotes point to where the quote was written
+                        let diagnostic = Diagnostic::new(&e);
+                        let original = diagnostic
+                            .span()
+                            .and_then(|span| interpreter.source(span.file()))
+                            .unwrap_or(&source);
+                        println!("{}", diagnostic.render(original));
+                    }
+                }
+                source.clear();
+            }
+
+            // ctrl-c throws away whatever has been typed so far
+            Err(ReadlineError::Interrupted) => source.clear(),
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
+
+// Check if a source has more opening braces or parens than closing ones.
+fn is_unbalanced(source: &str) -> bool {
+    let mut scanner = Scanner::new(source);
+    let mut depth = 0isize;
+
+    loop {
+        // anything that doesn't scan is left for the interpreter to report
+        let token = match scanner.next_token() {
+            Ok(token) => token,
+            Err(_) => return false,
+        };
+
+        match token.kind() {
+            TokenKind::LeftBrace | TokenKind::LeftParen => depth += 1,
+            TokenKind::RightBrace | TokenKind::RightParen => depth -= 1,
+            TokenKind::EOF => break,
+            _ => {}
+        }
+    }
+
+    depth > 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unbalanced1() {
+        assert!(!is_unbalanced("1 + 2\n"));
+        assert!(!is_unbalanced("double ← {⍵ + ⍵}\n"));
+        assert!(is_unbalanced("double ← {⍵ +\n"));
+        assert!(is_unbalanced("x ← (1 2\n (3 4\n"));
+        assert!(!is_unbalanced("x ← (1 2\n (3 4)\n 5)\n"));
+        assert!(!is_unbalanced("1 + 2)\n"));
+        assert!(!is_unbalanced("x ← ('abc\n"));
+        assert!(!is_unbalanced("{⍵ + 'a\n"));
+    }
+}
@@ -1,11 +1,22 @@
-use crate::prelude::Token;
+//! parser
 
+use crate::prelude::{anyhow, ErrorKind, FileId, Result, Scanner, Span, Token, TokenKind};
+
+use std::collections::HashSet;
+
+/// A single statement, separated from others by `⋄` or a newline.
+#[derive(Debug, PartialEq, Clone)]
 pub enum Statement {
     Assign { name: Token, expression: Expression },
     AssignQuote { name: Token, expression: Expression },
     Expression(Expression),
 }
 
+/// Expressions
+///
+/// Sponk is read right to left, so `lhs` is always the argument a verb sees first (⍵, written on its right), and
+/// `rhs` is the left argument (⍺) of a dyad.
+#[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
     Ident {
         name: Token,
@@ -13,6 +24,9 @@ pub enum Expression {
     List {
         value: Vec<Token>,
     },
+    Strand {
+        items: Vec<Expression>,
+    },
     MonadCall {
         op: Box<Expression>,
         lhs: Box<Expression>,
@@ -37,11 +51,636 @@ pub enum Expression {
     Lambda {
         left_brace: Token,
         expression: Box<Expression>,
+        right_brace: Token,
+    },
+    // a[i;j], where an empty index selects everything along its axis
+    Index {
+        array: Box<Expression>,
+        left_bracket: Token,
+        indices: Vec<Option<Expression>>,
+        right_bracket: Token,
+    },
+    // f[k], a builtin function along an axis
+    Axis {
+        verb: Box<Expression>,
+        left_bracket: Token,
+        axis: Box<Expression>,
+        right_bracket: Token,
     },
 }
 
-pub fn parse() -> Option<Statement> {
-    None
+impl Expression {
+    /// Get the span of source the expression was parsed from.
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Ident { name } => name.span(),
+            Expression::List { value } => value[0].span().merge(value[value.len() - 1].span()),
+            Expression::Strand { items } => items[0].span().merge(items[items.len() - 1].span()),
+            Expression::MonadCall { op, lhs } => op.span().merge(lhs.span()),
+            Expression::DyadCall { rhs, lhs, .. } => rhs.span().merge(lhs.span()),
+            Expression::Quote { colon, expression } => colon.span().merge(expression.span()),
+            Expression::Call { expression, colon } => expression.span().merge(colon.span()),
+            Expression::Spread { verb, slash } => verb.span().merge(slash.span()),
+            Expression::Lambda {
+                left_brace,
+                right_brace,
+                ..
+            } => left_brace.span().merge(right_brace.span()),
+            Expression::Index {
+                array,
+                right_bracket,
+                ..
+            } => array.span().merge(right_bracket.span()),
+            Expression::Axis {
+                verb,
+                right_bracket,
+                ..
+            } => verb.span().merge(right_bracket.span()),
+        }
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", pieces(self).concat())
+    }
+}
+
+// Write an expression compactly, the way it would be typed.
+fn pieces(expression: &Expression) -> Vec<String> {
+    // write a sub-expression, parenthesized if necessary
+    let grouped = |expression: &Expression| match expression {
+        Expression::MonadCall { .. } | Expression::DyadCall { .. } | Expression::Strand { .. } => {
+            vec![format!("({})", expression)]
+        }
+        Expression::List { value } if value.len() > 1 => vec![format!("({})", expression)],
+        expression => pieces(expression),
+    };
+
+    let mut pieces = match expression {
+        Expression::Ident { name } => vec![name.lexeme().to_string()],
+        Expression::List { value } => value
+            .iter()
+            .map(|token| token.lexeme().to_string())
+            .collect(),
+        Expression::Strand { items } => items.iter().flat_map(grouped).collect(),
+        Expression::MonadCall { op, lhs } => [pieces(op), pieces(lhs)].concat(),
+        Expression::DyadCall { rhs, op, lhs } => [grouped(rhs), pieces(op), pieces(lhs)].concat(),
+        Expression::Quote { colon, expression } => {
+            [vec![colon.lexeme().to_string()], pieces(expression)].concat()
+        }
+        Expression::Call { expression, colon } => {
+            [pieces(expression), vec![colon.lexeme().to_string()]].concat()
+        }
+        Expression::Spread { verb, slash } => {
+            [pieces(verb), vec![slash.lexeme().to_string()]].concat()
+        }
+        Expression::Lambda { expression, .. } => vec![format!("{{{}}}", expression)],
+        Expression::Index { array, indices, .. } => {
+            let indices: Vec<String> = indices
+                .iter()
+                .map(|index| index.as_ref().map(ToString::to_string).unwrap_or_default())
+                .collect();
+            [grouped(array), vec![format!("[{}]", indices.join(";"))]].concat()
+        }
+        Expression::Axis { verb, axis, .. } => [pieces(verb), vec![format!("[{}]", axis)]].concat(),
+    };
+
+    // separate words that would otherwise run together
+    for i in 1..pieces.len() {
+        let wordy =
+            |c: Option<char>| c.map(|c| c.is_alphanumeric() || "¯'.".contains(c)) == Some(true);
+        if wordy(pieces[i - 1].chars().last()) && wordy(pieces[i].chars().next()) {
+            pieces[i].insert(0, ' ');
+        }
+    }
+
+    pieces
+}
+
+/// Names known to be bound to functions.
+///
+/// `f x` is a call if `f` is a function and a strand if it isn't, so the parser needs to know which names have had
+/// verbs assigned to them.
+#[derive(Debug, Default, Clone)]
+pub struct SymbolTable {
+    functions: HashSet<String>,
+}
+
+impl SymbolTable {
+    /// Create an empty symbol table.
+    pub fn new() -> SymbolTable {
+        SymbolTable::default()
+    }
+
+    /// Check if a name refers to a function.
+    pub fn is_function(&self, name: &str) -> bool {
+        self.functions.contains(name)
+    }
+
+    /// Record that a name refers to a function.
+    pub fn define_function(&mut self, name: &str) {
+        self.functions.insert(name.to_string());
+    }
+
+    /// Record that a name refers to a value.
+    pub fn define_value(&mut self, name: &str) {
+        self.functions.remove(name);
+    }
+}
+
+/// Parse a source into statements.
+pub fn parse(source: &str) -> Result<Vec<Statement>> {
+    parse_with(source, FileId::default(), &mut SymbolTable::new())
+}
+
+/// Parse a source from a file into statements, using and updating the function names in `symbols`.
+pub fn parse_with(source: &str, file: FileId, symbols: &mut SymbolTable) -> Result<Vec<Statement>> {
+    let mut scanner = Scanner::with_file(source, file);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = scanner.next_token()?;
+        if token.kind() == TokenKind::EOF {
+            break;
+        }
+        tokens.push(token);
+    }
+
+    split_statements(&tokens)
+        .into_iter()
+        .map(|tokens| statement(tokens, symbols))
+        .collect()
+}
+
+fn syntax_error(why: impl std::fmt::Display, span: Span) -> anyhow::Error {
+    anyhow!(ErrorKind::SyntaxError {
+        why: anyhow!("{}", why),
+        span,
+    })
+}
+
+// Split tokens into statements on ⋄ and on newlines outside of any brackets.
+fn split_statements(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut statements = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let new_line = i > 0 && token.span().line() != tokens[i - 1].span().line();
+        if depth == 0 && new_line && start < i {
+            statements.push(&tokens[start..i]);
+            start = i;
+        }
+
+        match token.kind() {
+            TokenKind::LeftParen | TokenKind::LeftBrace | TokenKind::LeftBracket => depth += 1,
+            TokenKind::RightParen | TokenKind::RightBrace | TokenKind::RightBracket => {
+                depth = depth.saturating_sub(1)
+            }
+            TokenKind::Builtin if depth == 0 && token.lexeme() == "⋄" => {
+                if start < i {
+                    statements.push(&tokens[start..i]);
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if start < tokens.len() {
+        statements.push(&tokens[start..]);
+    }
+
+    statements
+}
+
+// Parse a statement, which is either an assignment or an expression.
+fn statement(tokens: &[Token], symbols: &mut SymbolTable) -> Result<Statement> {
+    if let [name, arrow, rest @ ..] = tokens {
+        if name.kind() == TokenKind::Ident && arrow.lexeme() == "←" {
+            // allow recursive functions to refer to themselves
+            if rest.first().map(Token::kind) == Some(TokenKind::LeftBrace) {
+                symbols.define_function(name.lexeme());
+            }
+
+            let unit = Parser::new(rest, arrow.span(), symbols).unit()?;
+            if unit.is_verb() {
+                symbols.define_function(name.lexeme());
+            } else {
+                symbols.define_value(name.lexeme());
+            }
+
+            return Ok(Statement::Assign {
+                name: name.clone(),
+                expression: unit.into_expression(),
+            });
+        }
+    }
+
+    let anchor = tokens[0].span();
+    Ok(Statement::Expression(
+        Parser::new(tokens, anchor, symbols).expression()?,
+    ))
+}
+
+// Part of an expression before calls have been resolved.
+enum Unit {
+    Literal(Token),
+    Noun(Expression),
+    Verb(Expression),
+}
+
+impl Unit {
+    fn is_verb(&self) -> bool {
+        matches!(self, Unit::Verb(_))
+    }
+
+    fn is_lambda(&self) -> bool {
+        matches!(self, Unit::Verb(Expression::Lambda { .. }))
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Unit::Literal(token) => token.span(),
+            Unit::Noun(expression) | Unit::Verb(expression) => expression.span(),
+        }
+    }
+
+    fn into_expression(self) -> Expression {
+        match self {
+            Unit::Literal(token) => Expression::List { value: vec![token] },
+            Unit::Noun(expression) | Unit::Verb(expression) => expression,
+        }
+    }
+}
+
+// Glyphs that modify the verb on their left.
+fn is_operator(token: &Token) -> bool {
+    token.kind() == TokenKind::Builtin && matches!(token.lexeme(), "/" | "⌿" | "\\" | "⍀")
+}
+
+// The token that closes a bracket-like token.
+fn closer(kind: TokenKind) -> TokenKind {
+    match kind {
+        TokenKind::LeftParen => TokenKind::RightParen,
+        TokenKind::LeftBrace => TokenKind::RightBrace,
+        TokenKind::LeftBracket => TokenKind::RightBracket,
+        _ => unreachable!("not an opening token"),
+    }
+}
+
+// Parser for the tokens of a single expression.
+struct Parser<'t, 's> {
+    tokens: &'t [Token],
+    position: usize,
+    // where to point errors about empty expressions
+    anchor: Span,
+    symbols: &'s SymbolTable,
+}
+
+impl<'t, 's> Parser<'t, 's> {
+    fn new(tokens: &'t [Token], anchor: Span, symbols: &'s SymbolTable) -> Parser<'t, 's> {
+        Parser {
+            tokens,
+            position: 0,
+            anchor,
+            symbols,
+        }
+    }
+
+    fn next(&mut self) -> Option<&'t Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.get(self.position)
+    }
+
+    // Parse the whole token slice as one expression.
+    fn expression(&mut self) -> Result<Expression> {
+        Ok(self.unit()?.into_expression())
+    }
+
+    // Parse the whole token slice, keeping track of whether it came out as a verb or a noun.
+    //
+    // Verbs have long right scope and short left scope: everything to the right of a verb is its right argument, and
+    // only the noun or strand directly to its left is its left argument.
+    fn unit(&mut self) -> Result<Unit> {
+        let mut units = self.units()?;
+
+        if units.is_empty() {
+            return Err(syntax_error("expected an expression", self.anchor));
+        }
+
+        // a verb by itself is just a verb
+        if units.len() == 1 && units[0].is_verb() {
+            return Ok(units.pop().unwrap());
+        }
+
+        let mut value = match take_noun(&mut units) {
+            Some(noun) => noun,
+            None => {
+                return Err(syntax_error(
+                    "missing right argument",
+                    units.last().unwrap().span(),
+                ))
+            }
+        };
+
+        while let Some(unit) = units.pop() {
+            let op = match unit {
+                Unit::Verb(verb) => verb,
+                unit => return Err(syntax_error("expected a function", unit.span())),
+            };
+
+            // quotes to the left of a verb are passed as its left argument
+            let rhs = match units.last() {
+                Some(unit) if unit.is_lambda() => Some(units.pop().unwrap().into_expression()),
+                Some(Unit::Literal(_)) | Some(Unit::Noun(_)) => take_noun(&mut units),
+                _ => None,
+            };
+
+            value = match rhs {
+                Some(rhs) => Expression::DyadCall {
+                    rhs: Box::new(rhs),
+                    op: Box::new(op),
+                    lhs: Box::new(value),
+                },
+                None => Expression::MonadCall {
+                    op: Box::new(op),
+                    lhs: Box::new(value),
+                },
+            };
+        }
+
+        Ok(Unit::Noun(value))
+    }
+
+    // Split the tokens into nouns and verbs, from left to right.
+    fn units(&mut self) -> Result<Vec<Unit>> {
+        let mut units = Vec::new();
+
+        while let Some(token) = self.next() {
+            let unit = match token.kind() {
+                TokenKind::Int(_)
+                | TokenKind::BigInt
+                | TokenKind::Rational
+                | TokenKind::Float(_)
+                | TokenKind::Complex(_, _)
+                | TokenKind::String
+                | TokenKind::Atom => Unit::Literal(token.clone()),
+
+                TokenKind::Ident => {
+                    let ident = Expression::Ident {
+                        name: token.clone(),
+                    };
+                    if self.symbols.is_function(token.lexeme()) {
+                        Unit::Verb(ident)
+                    } else {
+                        Unit::Noun(ident)
+                    }
+                }
+
+                TokenKind::LeftParen => {
+                    let (inner, _) = self.group(token)?;
+                    Parser::new(inner, token.span(), self.symbols).unit()?
+                }
+
+                TokenKind::LeftBrace => {
+                    let (inner, right_brace) = self.group(token)?;
+                    Unit::Verb(Expression::Lambda {
+                        left_brace: token.clone(),
+                        expression: Box::new(
+                            Parser::new(inner, token.span(), self.symbols).expression()?,
+                        ),
+                        right_brace: right_brace.clone(),
+                    })
+                }
+
+                // brackets index the noun right before them
+                TokenKind::LeftBracket
+                    if matches!(units.last(), Some(Unit::Literal(_)) | Some(Unit::Noun(_))) =>
+                {
+                    let (inner, right_bracket) = self.group(token)?;
+                    let array = match units.pop().unwrap() {
+                        // a[i] indexes all of a literal vector, not just its last number
+                        Unit::Literal(last) => {
+                            let start = units
+                                .iter()
+                                .rposition(|unit| !matches!(unit, Unit::Literal(_)))
+                                .map(|i| i + 1)
+                                .unwrap_or(0);
+                            let mut value: Vec<Token> = units
+                                .drain(start..)
+                                .map(|unit| match unit {
+                                    Unit::Literal(token) => token,
+                                    _ => unreachable!(),
+                                })
+                                .collect();
+                            value.push(last);
+                            Expression::List { value }
+                        }
+                        unit => unit.into_expression(),
+                    };
+
+                    Unit::Noun(Expression::Index {
+                        array: Box::new(array),
+                        left_bracket: token.clone(),
+                        indices: self.indices(inner, token)?,
+                        right_bracket: right_bracket.clone(),
+                    })
+                }
+
+                // and give the axis of the builtin function right before them
+                TokenKind::LeftBracket
+                    if matches!(units.last(), Some(Unit::Verb(Expression::Ident { name }))
+                        if name.kind() == TokenKind::Builtin && self.tokens[self.position - 2] == *name) =>
+                {
+                    let (inner, right_bracket) = self.group(token)?;
+                    if inner.iter().all(|token| token.kind() == TokenKind::Comment) {
+                        return Err(syntax_error("missing axis", token.span()));
+                    }
+
+                    Unit::Verb(Expression::Axis {
+                        verb: Box::new(units.pop().unwrap().into_expression()),
+                        left_bracket: token.clone(),
+                        axis: Box::new(
+                            Parser::new(inner, token.span(), self.symbols).expression()?,
+                        ),
+                        right_bracket: right_bracket.clone(),
+                    })
+                }
+
+                TokenKind::LeftBracket
+                | TokenKind::RightParen
+                | TokenKind::RightBrace
+                | TokenKind::RightBracket => {
+                    return Err(syntax_error(
+                        format!("unexpected '{}'", token.lexeme()),
+                        token.span(),
+                    ))
+                }
+
+                TokenKind::Builtin => {
+                    let ident = Expression::Ident {
+                        name: token.clone(),
+                    };
+                    match token.lexeme() {
+                        // arguments are verbs when an operator is applied to them
+                        "⍺" | "⍵" if self.peek().map(is_operator) == Some(true) => {
+                            Unit::Verb(ident)
+                        }
+                        "⍺" | "⍵" | "⍬" => Unit::Noun(ident),
+                        "←" => {
+                            return Err(syntax_error(
+                                "can only assign to a name at the start of a statement",
+                                token.span(),
+                            ))
+                        }
+                        ";" | "⋄" => {
+                            return Err(syntax_error(
+                                format!("unexpected '{}'", token.lexeme()),
+                                token.span(),
+                            ))
+                        }
+                        _ => Unit::Verb(ident),
+                    }
+                }
+
+                // comments are trivia
+                TokenKind::Comment => continue,
+                TokenKind::EOF => break,
+            };
+
+            let unit = self.operators(unit);
+            units.push(unit);
+        }
+
+        Ok(units)
+    }
+
+    // Apply any operators following a verb to it.
+    fn operators(&mut self, mut unit: Unit) -> Unit {
+        while let Unit::Verb(verb) = unit {
+            match self.peek() {
+                Some(slash) if is_operator(slash) => {
+                    self.position += 1;
+                    unit = Unit::Verb(Expression::Spread {
+                        verb: Box::new(verb),
+                        slash: slash.clone(),
+                    });
+                }
+                _ => return Unit::Verb(verb),
+            }
+        }
+
+        unit
+    }
+
+    // Parse the indices between brackets, which are separated by ; and may be empty.
+    fn indices(&self, inner: &'t [Token], left_bracket: &Token) -> Result<Vec<Option<Expression>>> {
+        let mut indices = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+
+        for (i, token) in inner.iter().enumerate() {
+            match token.kind() {
+                TokenKind::LeftParen | TokenKind::LeftBrace | TokenKind::LeftBracket => depth += 1,
+                TokenKind::RightParen | TokenKind::RightBrace | TokenKind::RightBracket => {
+                    depth = depth.saturating_sub(1)
+                }
+                TokenKind::Builtin if depth == 0 && token.lexeme() == ";" => {
+                    indices.push(self.index(&inner[start..i], token.span())?);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+
+        indices.push(self.index(&inner[start..], left_bracket.span())?);
+        Ok(indices)
+    }
+
+    // Parse a single index, which selects everything if it's empty.
+    fn index(&self, tokens: &'t [Token], anchor: Span) -> Result<Option<Expression>> {
+        if tokens
+            .iter()
+            .all(|token| token.kind() == TokenKind::Comment)
+        {
+            Ok(None)
+        } else {
+            Ok(Some(
+                Parser::new(tokens, anchor, self.symbols).expression()?,
+            ))
+        }
+    }
+
+    // Get the tokens between an opening token and its matching closing token, and the closing token.
+    fn group(&mut self, open: &Token) -> Result<(&'t [Token], &'t Token)> {
+        let start = self.position;
+        let mut expected = vec![closer(open.kind())];
+
+        while let Some(token) = self.next() {
+            match token.kind() {
+                TokenKind::LeftParen | TokenKind::LeftBrace | TokenKind::LeftBracket => {
+                    expected.push(closer(token.kind()))
+                }
+                TokenKind::RightParen | TokenKind::RightBrace | TokenKind::RightBracket => {
+                    if expected.pop() != Some(token.kind()) {
+                        return Err(syntax_error(
+                            format!("mismatched '{}'", token.lexeme()),
+                            token.span(),
+                        ));
+                    }
+                    if expected.is_empty() {
+                        return Ok((&self.tokens[start..self.position - 1], token));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(syntax_error(
+            format!("unclosed '{}'", open.lexeme()),
+            open.span(),
+        ))
+    }
+}
+
+// Take the strand of nouns at the end of the units, if there is one.
+fn take_noun(units: &mut Vec<Unit>) -> Option<Expression> {
+    let start = units
+        .iter()
+        .rposition(|unit| unit.is_verb())
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    let mut nouns = units.split_off(start);
+    if nouns.is_empty() {
+        return None;
+    }
+
+    if nouns.len() == 1 {
+        return nouns.pop().map(Unit::into_expression);
+    }
+
+    if nouns.iter().all(|noun| matches!(noun, Unit::Literal(_))) {
+        let value = nouns
+            .into_iter()
+            .map(|noun| match noun {
+                Unit::Literal(token) => token,
+                _ => unreachable!(),
+            })
+            .collect();
+        return Some(Expression::List { value });
+    }
+
+    Some(Expression::Strand {
+        items: nouns.into_iter().map(Unit::into_expression).collect(),
+    })
 }
 
 #[cfg(test)]
@@ -53,14 +692,11 @@ mod test {
         let s = Scanner::new("x+y");
         let v: Vec<_> = s.collect();
         v.into_iter()
-            .zip(
-                vec![
-                    Token::no_span(TokenKind::Ident, "x"),
-                    Token::no_span(TokenKind::Builtin, "+"),
-                    Token::no_span(TokenKind::Ident, "y"),
-                ]
-                .into_iter(),
-            )
+            .zip(vec![
+                Token::no_span(TokenKind::Ident, "x"),
+                Token::no_span(TokenKind::Builtin, "+"),
+                Token::no_span(TokenKind::Ident, "y"),
+            ])
             .map(|(a, b)| assert!(a.compare_no_span(b)))
             .for_each(drop);
     }
@@ -83,4 +719,215 @@ mod test {
             },
         };
     }
+
+    // Write an expression like the syntax trees in the readme.
+    fn sexp(expression: &Expression) -> String {
+        match expression {
+            Expression::Ident { name } => name.lexeme().to_string(),
+            Expression::List { value } => {
+                let value: Vec<_> = value.iter().map(Token::lexeme).collect();
+                if value.len() == 1 {
+                    value[0].to_string()
+                } else {
+                    format!("[{}]", value.join(" "))
+                }
+            }
+            Expression::Strand { items } => {
+                let items: Vec<_> = items.iter().map(sexp).collect();
+                format!("[{}]", items.join(" "))
+            }
+            Expression::MonadCall { op, lhs } => format!("({} {})", sexp(op), sexp(lhs)),
+            Expression::DyadCall { rhs, op, lhs } => {
+                format!("({} {} {})", sexp(op), sexp(rhs), sexp(lhs))
+            }
+            Expression::Spread { verb, slash } => format!("{}{}", sexp(verb), slash.lexeme()),
+            Expression::Lambda { expression, .. } => format!("{{{}}}", sexp(expression)),
+            Expression::Index { array, indices, .. } => {
+                let indices: Vec<_> = indices
+                    .iter()
+                    .map(|index| index.as_ref().map(sexp).unwrap_or_default())
+                    .collect();
+                format!("{}[{}]", sexp(array), indices.join(";"))
+            }
+            Expression::Axis { verb, axis, .. } => format!("{}[{}]", sexp(verb), sexp(axis)),
+            Expression::Quote { .. } | Expression::Call { .. } => unreachable!(),
+        }
+    }
+
+    fn parse_one(source: &str) -> String {
+        match &parse(source).unwrap()[..] {
+            [Statement::Expression(expression)] => sexp(expression),
+            [Statement::Assign { name, expression }] => {
+                format!("{} ← {}", name.lexeme(), sexp(expression))
+            }
+            _ => panic!("expected one statement"),
+        }
+    }
+
+    #[test]
+    fn parse3() {
+        assert_eq!(parse_one("x + y"), "(+ x y)");
+        assert_eq!(parse_one("1 2 3 × 4 5 6"), "(× [1 2 3] [4 5 6])");
+        assert_eq!(parse_one("⍴⍴ x"), "(⍴ (⍴ x))");
+        assert_eq!(parse_one("1 + 2 × 3"), "(+ 1 (× 2 3))");
+        assert_eq!(parse_one("(1 + 2) × 3"), "(× (+ 1 2) 3)");
+    }
+
+    #[test]
+    fn parse4() {
+        assert_eq!(parse_one("x ← 1 2 3 4 5"), "x ← [1 2 3 4 5]");
+        assert_eq!(parse_one("double ← {⍵ + ⍵}"), "double ← {(+ ⍵ ⍵)}");
+        assert_eq!(parse_one("{⍵ + ⍵} x"), "({(+ ⍵ ⍵)} x)");
+        assert_eq!(parse_one("{x + x}"), "{(+ x x)}");
+    }
+
+    #[test]
+    fn parse5() {
+        assert_eq!(parse_one("f ← {⍺/ 2*⍵}"), "f ← {(⍺/ (* 2 ⍵))}");
+        assert_eq!(parse_one("+/ 1 2 3 4 5"), "(+/ [1 2 3 4 5])");
+        assert_eq!(
+            parse_one("avg ← {(+/ ⍵) ÷ (⍴ ⍵)}"),
+            "avg ← {(÷ (+/ ⍵) (⍴ ⍵))}"
+        );
+    }
+
+    #[test]
+    fn parse6() {
+        let statements = parse("double ← {⍵ + ⍵}\ndouble double x ⋄ x double 3").unwrap();
+        assert_eq!(statements.len(), 3);
+        match &statements[1] {
+            Statement::Expression(expression) => {
+                assert_eq!(sexp(expression), "(double (double x))")
+            }
+            _ => panic!(),
+        }
+        match &statements[2] {
+            Statement::Expression(expression) => assert_eq!(sexp(expression), "(double x 3)"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse7() {
+        let mut symbols = SymbolTable::new();
+        parse_with("f ← {⍺/ 2*⍵}", FileId::default(), &mut symbols).unwrap();
+        match &parse_with("{1+⍵} f 1 2 3 4 5", FileId::default(), &mut symbols).unwrap()[..] {
+            [Statement::Expression(expression)] => {
+                assert_eq!(sexp(expression), "(f {(+ 1 ⍵)} [1 2 3 4 5])")
+            }
+            _ => panic!(),
+        }
+
+        // without knowing what f is, it's just a name in a strand
+        assert_eq!(parse_one("1 + f 1 2"), "(+ 1 [f 1 2])");
+    }
+
+    #[test]
+    fn parse8() {
+        assert_eq!(
+            parse_one("robert ← 'Robert Dufresne' 1992 6 4"),
+            "robert ← ['Robert Dufresne' 1992 6 4]"
+        );
+        assert_eq!(parse_one("(1 2) 3 (x)"), "[[1 2] 3 x]");
+        assert_eq!(parse_one("x ← (1 2\n 3 4)"), "x ← [1 2 3 4]");
+    }
+
+    #[test]
+    fn parse9() {
+        for source in [
+            "1 +",
+            "()",
+            "(1 2",
+            "1 2)",
+            "{1 + ⍵",
+            "1 + x ← 2",
+            "[1]",
+            "1 (+ 2",
+        ] {
+            let error = parse(source).err().unwrap();
+            assert!(
+                matches!(
+                    error.downcast_ref::<ErrorKind>(),
+                    Some(ErrorKind::SyntaxError { .. })
+                ),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn parse10() {
+        let source = "x ← 1 + {⍵ × 2} 3 4";
+        let expression = match &parse(source).unwrap()[..] {
+            [Statement::Assign { expression, .. }] => expression.clone(),
+            _ => panic!(),
+        };
+        assert_eq!(&source[expression.span().bytes()], "1 + {⍵ × 2} 3 4");
+        match expression {
+            Expression::DyadCall { lhs, .. } => match *lhs {
+                Expression::MonadCall { op, .. } => {
+                    assert_eq!(&source[op.span().bytes()], "{⍵ × 2}")
+                }
+                _ => panic!(),
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse11() {
+        let statements = parse(
+            "⍝ list manipulation
+            x ← 1 2 3 4 5
+            y ← 6 7 8 9 10 ⍝ (unbalanced in a comment
+            ⍝ append
+            x,y",
+        )
+        .unwrap();
+        assert_eq!(statements.len(), 3);
+    }
+
+    #[test]
+    fn parse12() {
+        assert_eq!(parse_one("+\\ 1 2 3"), "(+\\ [1 2 3])");
+        assert_eq!(parse_one("{⍺×⍵}⍀ x"), "({(× ⍺ ⍵)}⍀ x)");
+    }
+
+    #[test]
+    fn parse13() {
+        assert_eq!(parse_one("a[1;2]"), "a[1;2]");
+        assert_eq!(parse_one("a[;1 2]"), "a[;[1 2]]");
+        assert_eq!(parse_one("(- a)[2;(1+1);]"), "(- a)[2;(+ 1 1);]");
+        assert_eq!(parse_one("1 2 3[2]"), "[1 2 3][2]");
+        assert_eq!(parse_one("- a[1]"), "(- a[1])");
+        assert_eq!(parse_one("x a[b[1]]"), "[x a[b[1]]]");
+        assert!(parse("{⍵}[1]").is_err());
+        assert!(parse("a[1").is_err());
+
+        let source = "x ← a[2;2;3]";
+        let expression = match &parse(source).unwrap()[..] {
+            [Statement::Assign { expression, .. }] => expression.clone(),
+            _ => panic!(),
+        };
+        assert_eq!(&source[expression.span().bytes()], "a[2;2;3]");
+        assert_eq!(expression.to_string(), "a[2;2;3]");
+    }
+
+    #[test]
+    fn parse14() {
+        assert_eq!(parse_one("x ,[0.5] y"), "(,[0.5] x y)");
+        assert_eq!(parse_one("x ⍪[1+1] y"), "(⍪[(+ 1 1)] x y)");
+        assert_eq!(parse_one("+[1]/ a"), "(+[1]/ a)");
+        assert!(parse("(+)[1] a").is_err());
+        assert!(parse("x ,[] y").is_err());
+
+        let source = "z ← x ,[0.5] y";
+        let expression = match &parse(source).unwrap()[..] {
+            [Statement::Assign { expression, .. }] => expression.clone(),
+            _ => panic!(),
+        };
+        assert_eq!(&source[expression.span().bytes()], "x ,[0.5] y");
+        assert_eq!(expression.to_string(), "x,[0.5]y");
+    }
 }
@@ -0,0 +1,374 @@
+//! render
+//!
+//! Arrays are drawn in boxes, with arrows along the top and left showing the axes:
+//!
+//! ```text
+//! ┌→─────────┐
+//! ↓1 2 3 4  5│
+//! │6 7 8 9 10│
+//! └~─────────┘
+//! ```
+//!
+//! The character in the bottom left corner says what's in the array: `~` for numbers, `-` for strings, `∆` for atoms,
+//! `∊` if any elements are themselves arrays, and `+` for a mix.
+
+use crate::prelude::{Array, Element};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Configurable rendering of arrays as text
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    boxed: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer { boxed: true }
+    }
+}
+
+impl Renderer {
+    /// Create a renderer that draws boxes.
+    pub fn new() -> Renderer {
+        Renderer::default()
+    }
+
+    /// Set whether to draw boxes around arrays. Without boxes, nested arrays are only separated by spaces.
+    pub fn boxed(mut self, boxed: bool) -> Renderer {
+        self.boxed = boxed;
+        self
+    }
+
+    /// Render an array.
+    pub fn render(&self, array: &Array) -> String {
+        self.array(array).lines.join("\n")
+    }
+
+    /// Render a single element.
+    pub fn render_element(&self, element: &Element) -> String {
+        self.element(element).lines.join("\n")
+    }
+
+    fn element(&self, element: &Element) -> Block {
+        match element {
+            Element::Array(array) => self.array(array),
+            element => Block::text(scalar(element)),
+        }
+    }
+
+    fn array(&self, array: &Array) -> Block {
+        let shape = array.shape();
+        let data = array.data();
+
+        // scalars aren't boxed
+        if array.is_scalar() {
+            return self.element(&data[0]);
+        }
+
+        let columns = shape.last().copied().unwrap_or(1).max(1);
+        let cells: Vec<Block> = data.iter().map(|element| self.element(element)).collect();
+
+        // every cell in a column is the same width
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                cells
+                    .iter()
+                    .skip(column)
+                    .step_by(columns)
+                    .map(|cell| cell.width)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut body = Vec::new();
+        for (row, (cells, elements)) in cells.chunks(columns).zip(data.chunks(columns)).enumerate()
+        {
+            // separate the planes of arrays of rank 3 and up with blank lines
+            body.extend(std::iter::repeat_n(String::new(), blank_lines(shape, row)));
+
+            let height = cells.iter().map(|cell| cell.lines.len()).max().unwrap_or(0);
+            for line in 0..height {
+                let line: Vec<String> = cells
+                    .iter()
+                    .zip(elements)
+                    .zip(&widths)
+                    .map(|((cell, element), &width)| cell.line(line, width, element.is_number()))
+                    .collect();
+                body.push(line.join(" "));
+            }
+        }
+
+        let body = Block::lines(body);
+        if self.boxed {
+            frame(body, array)
+        } else {
+            body
+        }
+    }
+}
+
+// A rectangle of text.
+struct Block {
+    lines: Vec<String>,
+    width: usize,
+}
+
+impl Block {
+    fn text(text: String) -> Block {
+        Block::lines(text.lines().map(String::from).collect())
+    }
+
+    fn lines(lines: Vec<String>) -> Block {
+        let width = lines.iter().map(|line| width(line)).max().unwrap_or(0);
+        Block { lines, width }
+    }
+
+    // Get a line of the block padded out to a width, or blank if the block isn't that tall.
+    fn line(&self, line: usize, width: usize, align_right: bool) -> String {
+        let text = self.lines.get(line).map(String::as_str).unwrap_or("");
+        let padding = " ".repeat(width.saturating_sub(self::width(text)));
+        if align_right {
+            padding + text
+        } else {
+            String::from(text) + &padding
+        }
+    }
+}
+
+fn width(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+// Draw the box around the body of an array.
+fn frame(body: Block, array: &Array) -> Block {
+    let shape = array.shape();
+    let rank = shape.len();
+    let border = rank.saturating_sub(1).max(1);
+    let inner = body.width.max(1);
+
+    let across = if shape.last() == Some(&0) {
+        "⊖"
+    } else {
+        "→"
+    };
+    let down = if shape.first() == Some(&0) {
+        "⌽"
+    } else {
+        "↓"
+    };
+
+    // empty arrays of rank 2 and up still get a row, so the leading axes are marked
+    let body = if body.lines.is_empty() && rank > 1 {
+        Block::lines(vec![String::new()])
+    } else {
+        body
+    };
+
+    let mut lines = Vec::with_capacity(body.lines.len() + 2);
+    lines.push(format!(
+        "{}{}{}┐",
+        "┌".repeat(border),
+        across,
+        "─".repeat(inner - 1)
+    ));
+
+    for (i, line) in body.lines.iter().enumerate() {
+        let left = if i == 0 && rank > 1 { down } else { "│" };
+        lines.push(format!(
+            "{}{}{}│",
+            left.repeat(border),
+            line,
+            " ".repeat(inner - width(line))
+        ));
+    }
+
+    lines.push(format!(
+        "{}{}{}┘",
+        "└".repeat(border),
+        kind(array),
+        "─".repeat(inner - 1)
+    ));
+
+    Block::lines(lines)
+}
+
+// How many blank lines go before a row of an array.
+fn blank_lines(shape: &[usize], row: usize) -> usize {
+    if row == 0 || shape.len() < 3 {
+        return 0;
+    }
+
+    // one blank line for each plane boundary the row sits on
+    let mut size = 1;
+    let mut lines = 0;
+    for &length in shape[..shape.len() - 1].iter().rev().take(shape.len() - 2) {
+        size *= length;
+        if row.is_multiple_of(size) {
+            lines += 1;
+        } else {
+            break;
+        }
+    }
+
+    lines
+}
+
+// The character describing what's in an array.
+fn kind(array: &Array) -> &'static str {
+    let data = array.data();
+    if data.iter().any(|element| element.as_array().is_some()) {
+        "∊"
+    } else if data.iter().all(Element::is_number) {
+        "~"
+    } else if data
+        .iter()
+        .all(|element| matches!(element, Element::String(_)))
+    {
+        "-"
+    } else if data
+        .iter()
+        .all(|element| matches!(element, Element::Atom(_)))
+    {
+        "∆"
+    } else {
+        "+"
+    }
+}
+
+// Write out a single non-array element.
+fn scalar(element: &Element) -> String {
+    match element {
+        // negative numbers are written with a high minus
+        Element::Int(i) => i.to_string().replace('-', "¯"),
+        Element::BigInt(i) => i.to_string().replace('-', "¯"),
+        // rationals are written like J writes them, 3r4
+        Element::Rational(r) => format!("{}r{}", r.numer(), r.denom()).replace('-', "¯"),
+        Element::Float(f) => float(*f),
+        Element::Complex(c) => format!("{}J{}", float(c.re), float(c.im)),
+        Element::String(string) => format!("'{}'", string.replace('\'', "\\'")),
+        Element::Atom(atom) => atom.to_string(),
+        Element::None => String::new(),
+        Element::Array(_) => unreachable!("arrays aren't scalars"),
+    }
+}
+
+// Write out a float, without a trailing .0 if it's whole.
+fn float(f: f64) -> String {
+    if f.is_infinite() {
+        String::from(if f < 0.0 { "¯∞" } else { "∞" })
+    } else {
+        f.to_string().replace('-', "¯")
+    }
+}
+
+impl std::fmt::Display for Array {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Renderer::default().render(self))
+    }
+}
+
+impl std::fmt::Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Renderer::default().render_element(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::array::ints;
+
+    #[test]
+    fn render1() {
+        assert_eq!(
+            ints(&[5], &[7, 9, 11, 13, 15]).to_string(),
+            "┌→───────────┐\n\
+             │7 9 11 13 15│\n\
+             └~───────────┘"
+        );
+        assert_eq!(ints(&[], &[5]).to_string(), "5");
+        assert_eq!(ints(&[1], &[5]).to_string(), "┌→┐\n│5│\n└~┘");
+        assert_eq!(ints(&[2], &[-1, 2]).to_string(), "┌→───┐\n│¯1 2│\n└~───┘");
+    }
+
+    #[test]
+    fn render2() {
+        assert_eq!(
+            ints(&[2, 5], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).to_string(),
+            "┌→─────────┐\n\
+             ↓1 2 3 4  5│\n\
+             │6 7 8 9 10│\n\
+             └~─────────┘"
+        );
+    }
+
+    #[test]
+    fn render3() {
+        assert_eq!(
+            ints(
+                &[2, 2, 3],
+                &[26, 16, 22, 43, 36, 47, 44, 21, 58, 29, 19, 23]
+            )
+            .to_string(),
+            "┌┌→───────┐\n\
+             ↓↓26 16 22│\n\
+             ││43 36 47│\n\
+             ││        │\n\
+             ││44 21 58│\n\
+             ││29 19 23│\n\
+             └└~───────┘"
+        );
+    }
+
+    #[test]
+    fn render4() {
+        let nested = Array::vector(vec![
+            Element::String(String::from("Robert")),
+            Element::Array(ints(&[2], &[6, 4])),
+            Element::Int(1992),
+        ]);
+        assert_eq!(
+            nested.to_string(),
+            "┌→──────────────────┐\n\
+             │'Robert' ┌→──┐ 1992│\n\
+             │         │6 4│     │\n\
+             │         └~──┘     │\n\
+             └∊──────────────────┘"
+        );
+        assert_eq!(
+            Renderer::new().boxed(false).render(&nested),
+            "'Robert' 6 4 1992"
+        );
+    }
+
+    #[test]
+    fn render5() {
+        assert_eq!(Array::vector(Vec::new()).to_string(), "┌⊖┐\n└~┘");
+        assert_eq!(ints(&[0, 3], &[]).to_string(), "┌→┐\n⌽ │\n└~┘");
+        assert_eq!(ints(&[3, 0], &[]).to_string(), "┌⊖┐\n↓ │\n└~┘");
+        assert_eq!(
+            Array::vector(vec![
+                Element::String(String::from("a")),
+                Element::String(String::from("b"))
+            ])
+            .to_string(),
+            "┌→──────┐\n│'a' 'b'│\n└-──────┘"
+        );
+    }
+
+    #[test]
+    fn render6() {
+        assert_eq!(
+            Array::vector(vec![
+                Element::Float(16.8),
+                Element::Float(-0.5),
+                Element::Float(3.0),
+                Element::Complex(num_complex::Complex64::new(1.5, -2.0)),
+            ])
+            .to_string(),
+            "┌→─────────────────┐\n│16.8 ¯0.5 3 1.5J¯2│\n└~─────────────────┘"
+        );
+    }
+}
@@ -0,0 +1,832 @@
+//! scalar functions
+//!
+//! Scalar functions apply to each element of their arguments, all the way down into nested arrays.
+
+use crate::prelude::{anyhow, Array, Element, ErrorKind, Result};
+
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use std::convert::TryFrom;
+
+/// A scalar function of one argument
+pub type Monad = fn(&Element) -> Result<Element>;
+
+/// A scalar function of two arguments
+pub type Dyad = fn(&Element, &Element) -> Result<Element>;
+
+/// Apply a scalar monad to every element of an array.
+pub fn monad(f: Monad, array: &Array) -> Result<Array> {
+    array.try_for_each_monad(|element| pervade_monad(f, element))
+}
+
+/// Apply a scalar dyad to every pair of elements of two arrays.
+pub fn dyad(f: Dyad, left: &Array, right: &Array) -> Result<Array> {
+    left.try_for_each_dyad(|l, r| pervade_dyad(f, l, r), right)
+}
+
+fn pervade_monad(f: Monad, element: &Element) -> Result<Element> {
+    match element {
+        Element::Array(array) => Ok(Element::Array(monad(f, array)?)),
+        element => f(element),
+    }
+}
+
+fn pervade_dyad(f: Dyad, left: &Element, right: &Element) -> Result<Element> {
+    match (left, right) {
+        (Element::Array(l), Element::Array(r)) => Ok(Element::Array(dyad(f, l, r)?)),
+        (Element::Array(l), r) => Ok(Element::Array(dyad(f, l, &Array::from_element(r.clone()))?)),
+        (l, Element::Array(r)) => Ok(Element::Array(dyad(f, &Array::from_element(l.clone()), r)?)),
+        (l, r) => f(l, r),
+    }
+}
+
+fn domain_error(why: impl ToString) -> anyhow::Error {
+    anyhow!(ErrorKind::DomainError {
+        why: why.to_string(),
+    })
+}
+
+// The numeric tower. Operands are promoted to the higher of their two levels before a dyad is applied, and integer
+// results that overflow are promoted to big integers.
+#[derive(Debug, Clone, PartialEq)]
+enum Number {
+    Int(i64),
+    BigInt(BigInt),
+    Rational(BigRational),
+    Float(f64),
+    Complex(Complex64),
+}
+
+impl Number {
+    fn big(&self) -> BigInt {
+        match self {
+            Number::Int(i) => BigInt::from(*i),
+            Number::BigInt(i) => i.clone(),
+            _ => unreachable!("not an integer"),
+        }
+    }
+
+    fn ratio(&self) -> BigRational {
+        match self {
+            Number::Rational(r) => r.clone(),
+            number => BigRational::from_integer(number.big()),
+        }
+    }
+
+    fn float(&self) -> f64 {
+        match self {
+            Number::Int(i) => *i as f64,
+            Number::BigInt(i) => i.to_f64().unwrap_or(f64::NAN),
+            Number::Rational(r) => r.to_f64().unwrap_or(f64::NAN),
+            Number::Float(f) => *f,
+            Number::Complex(c) => c.re,
+        }
+    }
+
+    fn complex(&self) -> Complex64 {
+        match self {
+            Number::Complex(c) => *c,
+            number => Complex64::new(number.float(), 0.0),
+        }
+    }
+
+    fn level(&self) -> u8 {
+        match self {
+            Number::Int(_) => 0,
+            Number::BigInt(_) => 1,
+            Number::Rational(_) => 2,
+            Number::Float(_) => 3,
+            Number::Complex(_) => 4,
+        }
+    }
+
+    // Bring a number up to a level of the tower.
+    fn promote(self, level: u8) -> Number {
+        if self.level() == level {
+            return self;
+        }
+
+        match level {
+            1 => Number::BigInt(self.big()),
+            2 => Number::Rational(self.ratio()),
+            3 => Number::Float(self.float()),
+            _ => Number::Complex(self.complex()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match self {
+            Number::Int(i) => *i == 0,
+            Number::BigInt(i) => i.is_zero(),
+            Number::Rational(r) => r.is_zero(),
+            Number::Float(f) => *f == 0.0,
+            Number::Complex(c) => c.is_zero(),
+        }
+    }
+}
+
+impl From<Number> for Element {
+    fn from(number: Number) -> Element {
+        match number {
+            Number::Int(i) => Element::Int(i),
+            Number::BigInt(i) => integer(i),
+            Number::Rational(r) => rational(r),
+            Number::Float(f) => Element::Float(f),
+            // complex numbers with no imaginary part are real
+            Number::Complex(c) if c.im == 0.0 => Element::Float(c.re),
+            Number::Complex(c) => Element::Complex(c),
+        }
+    }
+}
+
+/// Create an integer element, which is only big if it needs to be.
+pub fn integer(i: BigInt) -> Element {
+    match i.to_i64() {
+        Some(i) => Element::Int(i),
+        None => Element::BigInt(i),
+    }
+}
+
+/// Create a rational element, which is an integer if it has no fractional part.
+pub fn rational(r: BigRational) -> Element {
+    if r.is_integer() {
+        integer(r.to_integer())
+    } else {
+        Element::Rational(r)
+    }
+}
+
+fn number(element: &Element) -> Result<Number> {
+    match element {
+        Element::Int(i) => Ok(Number::Int(*i)),
+        Element::BigInt(i) => Ok(Number::BigInt(i.clone())),
+        Element::Rational(r) => Ok(Number::Rational(r.clone())),
+        Element::Float(f) => Ok(Number::Float(*f)),
+        Element::Complex(c) => Ok(Number::Complex(*c)),
+        _ => Err(domain_error("expected a number")),
+    }
+}
+
+// Get two numbers at the same level of the tower.
+fn numbers(left: &Element, right: &Element) -> Result<(Number, Number)> {
+    let (left, right) = (number(left)?, number(right)?);
+    let level = left.level().max(right.level());
+    Ok((left.promote(level), right.promote(level)))
+}
+
+fn real(element: &Element) -> Result<Number> {
+    match number(element)? {
+        Number::Complex(_) => Err(domain_error("expected a real number")),
+        number => Ok(number),
+    }
+}
+
+fn reals(left: &Element, right: &Element) -> Result<(Number, Number)> {
+    real(left)?;
+    real(right)?;
+    numbers(left, right)
+}
+
+fn boolean(element: &Element) -> Result<bool> {
+    match element {
+        Element::Int(0) => Ok(false),
+        Element::Int(1) => Ok(true),
+        Element::Float(f) if *f == 0.0 => Ok(false),
+        Element::Float(f) if *f == 1.0 => Ok(true),
+        _ => Err(domain_error("expected a boolean")),
+    }
+}
+
+// Apply an arithmetic dyad at whatever level of the tower its arguments are at. Integer overflow goes to big integers.
+fn arithmetic(
+    left: &Element,
+    right: &Element,
+    int: fn(i64, i64) -> Option<i64>,
+    big: fn(BigInt, BigInt) -> BigInt,
+    ratio: fn(BigRational, BigRational) -> BigRational,
+    float: fn(f64, f64) -> f64,
+    complex: fn(Complex64, Complex64) -> Complex64,
+) -> Result<Element> {
+    Ok(match numbers(left, right)? {
+        (Number::Int(l), Number::Int(r)) => match int(l, r) {
+            Some(i) => Element::Int(i),
+            None => integer(big(l.into(), r.into())),
+        },
+        (Number::BigInt(l), Number::BigInt(r)) => integer(big(l, r)),
+        (Number::Rational(l), Number::Rational(r)) => rational(ratio(l, r)),
+        (Number::Float(l), Number::Float(r)) => Element::Float(float(l, r)),
+        (l, r) => Number::Complex(complex(l.complex(), r.complex())).into(),
+    })
+}
+
+// Turn a float into an integer if it is one and fits.
+fn integral(f: f64) -> Element {
+    if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+        Element::Int(f as i64)
+    } else {
+        Element::Float(f)
+    }
+}
+
+// Compare two real numbers, which are equal if they're the same the way [`same`] says.
+fn compare(left: &Element, right: &Element) -> Result<std::cmp::Ordering> {
+    if same(left, right) {
+        real(left)?;
+        real(right)?;
+        return Ok(std::cmp::Ordering::Equal);
+    }
+
+    let ordering = match reals(left, right)? {
+        (Number::Int(l), Number::Int(r)) => Some(l.cmp(&r)),
+        (Number::BigInt(l), Number::BigInt(r)) => Some(l.cmp(&r)),
+        (Number::Rational(l), Number::Rational(r)) => Some(l.cmp(&r)),
+        (l, r) => l.float().partial_cmp(&r.float()),
+    };
+    ordering.ok_or_else(|| domain_error("can't compare NaN"))
+}
+
+/// Order two numbers totally, with complex numbers ordered by their real and then imaginary parts. Anything that isn't
+/// a number is equal to everything.
+pub fn order(left: &Element, right: &Element) -> std::cmp::Ordering {
+    // -0 is the same as 0
+    let floats = |l: f64, r: f64| (l + 0.0).total_cmp(&(r + 0.0));
+    match numbers(left, right) {
+        Ok((Number::Int(l), Number::Int(r))) => l.cmp(&r),
+        Ok((Number::BigInt(l), Number::BigInt(r))) => l.cmp(&r),
+        Ok((Number::Rational(l), Number::Rational(r))) => l.cmp(&r),
+        Ok((Number::Float(l), Number::Float(r))) => floats(l, r),
+        Ok((l, r)) => {
+            let (l, r) = (l.complex(), r.complex());
+            floats(l.re, r.re).then(floats(l.im, r.im))
+        }
+        Err(_) => std::cmp::Ordering::Equal,
+    }
+}
+
+fn from_bool(b: bool) -> Element {
+    Element::Int(b as i64)
+}
+
+/// monad +
+pub fn conjugate(element: &Element) -> Result<Element> {
+    Ok(match number(element)? {
+        Number::Complex(c) => Number::Complex(c.conj()),
+        number => number,
+    }
+    .into())
+}
+
+/// monad -
+pub fn negate(element: &Element) -> Result<Element> {
+    subtract(&Element::Int(0), element)
+}
+
+/// monad ×
+pub fn signum(element: &Element) -> Result<Element> {
+    Ok(match number(element)? {
+        // the signum of zero is zero, never ¯1 or NaN
+        number if number.is_zero() => Element::Int(0),
+        Number::Int(i) => Element::Int(i.signum()),
+        Number::BigInt(i) => integer(i.signum()),
+        Number::Rational(r) => rational(r.signum()),
+        Number::Float(f) => Element::Int(f.signum() as i64),
+        Number::Complex(c) => Number::Complex(c / c.norm()).into(),
+    })
+}
+
+/// monad *, e to the power of ⍵
+pub fn exponential(element: &Element) -> Result<Element> {
+    Ok(match number(element)? {
+        Number::Complex(c) => Number::Complex(c.exp()).into(),
+        number => Element::Float(number.float().exp()),
+    })
+}
+
+/// monad ÷
+pub fn reciprocal(element: &Element) -> Result<Element> {
+    divide(&Element::Int(1), element)
+}
+
+/// monad |
+pub fn magnitude(element: &Element) -> Result<Element> {
+    Ok(match number(element)? {
+        Number::Int(i) => match i.checked_abs() {
+            Some(i) => Element::Int(i),
+            None => integer(BigInt::from(i).abs()),
+        },
+        Number::BigInt(i) => integer(i.abs()),
+        Number::Rational(r) => rational(r.abs()),
+        Number::Float(f) => Element::Float(f.abs()),
+        Number::Complex(c) => Element::Float(c.norm()),
+    })
+}
+
+/// monad ⌈, where a float that's the same as a whole number is that number
+pub fn ceiling(element: &Element) -> Result<Element> {
+    Ok(match real(element)? {
+        Number::Rational(r) => rational(r.ceil()),
+        Number::Float(f) if tolerant(f, f.round()) => integral(f.round()),
+        Number::Float(f) => integral(f.ceil()),
+        number => number.into(),
+    })
+}
+
+/// monad ⌊, where a float that's the same as a whole number is that number
+pub fn floor(element: &Element) -> Result<Element> {
+    Ok(match real(element)? {
+        Number::Rational(r) => rational(r.floor()),
+        Number::Float(f) if tolerant(f, f.round()) => integral(f.round()),
+        Number::Float(f) => integral(f.floor()),
+        number => number.into(),
+    })
+}
+
+/// monad ~
+pub fn not(element: &Element) -> Result<Element> {
+    Ok(from_bool(!boolean(element)?))
+}
+
+/// dyad +
+pub fn add(left: &Element, right: &Element) -> Result<Element> {
+    arithmetic(
+        left,
+        right,
+        i64::checked_add,
+        |l, r| l + r,
+        |l, r| l + r,
+        |l, r| l + r,
+        |l, r| l + r,
+    )
+}
+
+/// dyad -
+pub fn subtract(left: &Element, right: &Element) -> Result<Element> {
+    arithmetic(
+        left,
+        right,
+        i64::checked_sub,
+        |l, r| l - r,
+        |l, r| l - r,
+        |l, r| l - r,
+        |l, r| l - r,
+    )
+}
+
+/// dyad ×
+pub fn multiply(left: &Element, right: &Element) -> Result<Element> {
+    arithmetic(
+        left,
+        right,
+        i64::checked_mul,
+        |l, r| l * r,
+        |l, r| l * r,
+        |l, r| l * r,
+        |l, r| l * r,
+    )
+}
+
+/// dyad ÷
+///
+/// Dividing integers gives an integer when the division is exact, and a float otherwise. Big integers and rationals
+/// stay exact.
+pub fn divide(left: &Element, right: &Element) -> Result<Element> {
+    match numbers(left, right)? {
+        (l, r) if l.is_zero() && r.is_zero() => Ok(Element::Int(1)),
+        (_, r) if r.is_zero() => Err(domain_error("divide by zero")),
+        (Number::Int(l), Number::Int(r)) if l.checked_rem(r).unwrap_or(0) == 0 => {
+            match l.checked_div(r) {
+                Some(i) => Ok(Element::Int(i)),
+                None => Ok(integer(BigInt::from(l) / r)),
+            }
+        }
+        (Number::Int(l), Number::Int(r)) => Ok(Element::Float(l as f64 / r as f64)),
+        (l @ Number::BigInt(_), r) | (l @ Number::Rational(_), r) => {
+            Ok(rational(l.ratio() / r.ratio()))
+        }
+        (Number::Float(l), Number::Float(r)) => Ok(Element::Float(l / r)),
+        (l, r) => Ok(Number::Complex(l.complex() / r.complex()).into()),
+    }
+}
+
+// How many bits the numerator or denominator of an exact power can have before it's a float instead.
+const MAX_EXACT_BITS: u64 = 1 << 20;
+
+/// dyad *, ⍺ to the power of ⍵
+///
+/// Exact numbers raised to integer powers stay exact, except that negative powers of integers are floats, like dividing
+/// them gives, and results too big to stay exact are floats. Negative numbers raised to fractional powers are complex.
+pub fn power(left: &Element, right: &Element) -> Result<Element> {
+    let (base, exponent) = (number(left)?, number(right)?);
+    match exponent {
+        Number::Int(e) if base.level() <= 2 => {
+            if base.is_zero() && e < 0 {
+                return Err(domain_error("can't raise zero to a negative power"));
+            }
+
+            // a base with more than one bit raised to a power has about that many times as many bits
+            let ratio = base.ratio();
+            let bits = ratio.numer().bits().max(ratio.denom().bits());
+            let exact = i32::try_from(e).ok().filter(|e| {
+                bits <= 1 || bits.saturating_mul(e.unsigned_abs().into()) <= MAX_EXACT_BITS
+            });
+            let e = match exact {
+                Some(e) => e,
+                None => return Ok(Element::Float(base.float().powf(e as f64))),
+            };
+
+            let raised = ratio.pow(e);
+            match base {
+                Number::Int(_) if e < 0 => Ok(Element::Float(raised.to_f64().unwrap_or(f64::NAN))),
+                _ => Ok(rational(raised)),
+            }
+        }
+        Number::Complex(_) => Ok(Number::Complex(base.complex().powc(exponent.complex())).into()),
+        exponent => match base {
+            Number::Complex(c) => Ok(Number::Complex(c.powc(exponent.complex())).into()),
+            base if base.float() < 0.0 && exponent.float().fract() != 0.0 => {
+                Ok(Number::Complex(base.complex().powc(exponent.complex())).into())
+            }
+            base => Ok(Element::Float(base.float().powf(exponent.float()))),
+        },
+    }
+}
+
+/// dyad |, the remainder of ⍵÷⍺ with the sign of ⍺
+pub fn residue(left: &Element, right: &Element) -> Result<Element> {
+    match reals(left, right)? {
+        (l, r) if l.is_zero() => Ok(r.into()),
+        (Number::Int(l), Number::Int(r)) => {
+            let rem = r.checked_rem(l).unwrap_or(0);
+            if rem != 0 && (rem < 0) != (l < 0) {
+                Ok(Element::Int(rem + l))
+            } else {
+                Ok(Element::Int(rem))
+            }
+        }
+        (Number::BigInt(l), Number::BigInt(r)) => Ok(integer(r.mod_floor(&l))),
+        (Number::Rational(l), Number::Rational(r)) => Ok(rational(&r - &l * (&r / &l).floor())),
+        (l, r) => {
+            let (l, r) = (l.float(), r.float());
+            Ok(Element::Float(r - l * (r / l).floor()))
+        }
+    }
+}
+
+/// dyad ⌈
+pub fn maximum(left: &Element, right: &Element) -> Result<Element> {
+    Ok(match compare(left, right)? {
+        std::cmp::Ordering::Less => right.clone(),
+        _ => left.clone(),
+    })
+}
+
+/// dyad ⌊
+pub fn minimum(left: &Element, right: &Element) -> Result<Element> {
+    Ok(match compare(left, right)? {
+        std::cmp::Ordering::Greater => right.clone(),
+        _ => left.clone(),
+    })
+}
+
+/// dyad <
+pub fn less(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(compare(left, right)?.is_lt()))
+}
+
+/// dyad ≤
+pub fn less_equal(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(compare(left, right)?.is_le()))
+}
+
+/// dyad =, comparing numbers the way [`same`] does
+pub fn equal(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(same(left, right)))
+}
+
+/// dyad ≥
+pub fn greater_equal(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(compare(left, right)?.is_ge()))
+}
+
+/// dyad >
+pub fn greater(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(compare(left, right)?.is_gt()))
+}
+
+/// dyad ≠
+pub fn not_equal(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(!same(left, right)))
+}
+
+/// How far apart two floats can be relative to the larger of them and still be equal.
+pub const TOLERANCE: f64 = 1e-14;
+
+/// Check if two elements are equal, comparing numbers by value.
+///
+/// Floats and complex numbers are equal if their real and imaginary parts are each within [`TOLERANCE`] of each
+/// other, since they're usually rounded. An exact number compared with a float is rounded to a float first, and two
+/// exact numbers are only equal if they have the same exact value.
+pub fn same(left: &Element, right: &Element) -> bool {
+    let (l, r) = match (number(left), number(right)) {
+        (Ok(l), Ok(r)) => (l, r),
+        _ => return left == right,
+    };
+
+    let is_exact = |number: &Number| number.level() < 3;
+    if is_exact(&l) && is_exact(&r) {
+        let level = l.level().max(r.level());
+        l.promote(level) == r.promote(level)
+    } else {
+        let (l, r) = (l.complex(), r.complex());
+        tolerant(l.re, r.re) && tolerant(l.im, r.im)
+    }
+}
+
+// Check if two floats are within the tolerance of each other.
+fn tolerant(left: f64, right: f64) -> bool {
+    left == right
+        || left.is_finite()
+            && right.is_finite()
+            && (left - right).abs() <= TOLERANCE * left.abs().max(right.abs())
+}
+
+/// dyad ∧
+pub fn and(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(boolean(left)? && boolean(right)?))
+}
+
+/// dyad ∨
+pub fn or(left: &Element, right: &Element) -> Result<Element> {
+    Ok(from_bool(boolean(left)? || boolean(right)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::array::ints;
+
+    fn vector(data: &[i64]) -> Array {
+        ints(&[data.len()], data)
+    }
+
+    #[test]
+    fn scalar1() {
+        assert_eq!(
+            dyad(add, &vector(&[1, 2, 3]), &vector(&[4, 5, 6])).unwrap(),
+            vector(&[5, 7, 9])
+        );
+        assert_eq!(
+            dyad(subtract, &vector(&[10]), &vector(&[1, 2, 3])).unwrap(),
+            vector(&[9, 8, 7])
+        );
+        assert!(dyad(add, &vector(&[1, 2]), &vector(&[1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn scalar2() {
+        assert_eq!(
+            dyad(residue, &vector(&[3]), &vector(&[-7, -1, 0, 1, 7])).unwrap(),
+            vector(&[2, 2, 0, 1, 1])
+        );
+        assert_eq!(
+            dyad(residue, &vector(&[-3]), &vector(&[7])).unwrap(),
+            vector(&[-2])
+        );
+        assert_eq!(
+            dyad(divide, &vector(&[0, 6]), &vector(&[0, 3])).unwrap(),
+            vector(&[1, 2])
+        );
+        assert!(dyad(divide, &vector(&[1]), &vector(&[0])).is_err());
+        assert_eq!(
+            monad(negate, &vector(&[i64::MIN])).unwrap(),
+            Array::vector(vec![Element::BigInt(-BigInt::from(i64::MIN))])
+        );
+    }
+
+    #[test]
+    fn scalar3() {
+        let nested = Array::vector(vec![Element::Int(1), Element::Array(vector(&[2, 3]))]);
+        assert_eq!(
+            monad(negate, &nested).unwrap(),
+            Array::vector(vec![Element::Int(-1), Element::Array(vector(&[-2, -3]))])
+        );
+        assert_eq!(
+            dyad(multiply, &vector(&[10]), &nested).unwrap(),
+            Array::vector(vec![Element::Int(10), Element::Array(vector(&[20, 30]))])
+        );
+    }
+
+    #[test]
+    fn scalar4() {
+        assert_eq!(
+            dyad(divide, &vector(&[84, 6]), &vector(&[5, 3])).unwrap(),
+            Array::vector(vec![Element::Float(16.8), Element::Int(2)])
+        );
+        assert_eq!(
+            dyad(
+                add,
+                &vector(&[1]),
+                &Array::vector(vec![Element::Float(0.5)])
+            )
+            .unwrap(),
+            Array::vector(vec![Element::Float(1.5)])
+        );
+        assert_eq!(
+            monad(floor, &Array::vector(vec![Element::Float(-2.5)])).unwrap(),
+            vector(&[-3])
+        );
+        assert_eq!(
+            dyad(
+                equal,
+                &vector(&[3]),
+                &Array::vector(vec![Element::Float(3.0)])
+            )
+            .unwrap(),
+            vector(&[1])
+        );
+    }
+
+    #[test]
+    fn scalar5() {
+        let i = Array::vector(vec![Element::Complex(Complex64::new(0.0, 1.0))]);
+        assert_eq!(
+            dyad(multiply, &i, &i).unwrap(),
+            Array::vector(vec![Element::Float(-1.0)])
+        );
+        assert_eq!(
+            dyad(add, &vector(&[3]), &i).unwrap(),
+            Array::vector(vec![Element::Complex(Complex64::new(3.0, 1.0))])
+        );
+        assert_eq!(
+            monad(
+                magnitude,
+                &Array::vector(vec![Element::Complex(Complex64::new(3.0, 4.0))])
+            )
+            .unwrap(),
+            Array::vector(vec![Element::Float(5.0)])
+        );
+        assert!(dyad(less, &vector(&[1]), &i).is_err());
+    }
+
+    #[test]
+    fn scalar6() {
+        let big = BigInt::from(i64::MAX) * 2i64;
+        assert_eq!(
+            dyad(multiply, &vector(&[i64::MAX]), &vector(&[2])).unwrap(),
+            Array::vector(vec![Element::BigInt(big.clone())])
+        );
+        assert_eq!(
+            dyad(
+                divide,
+                &Array::vector(vec![Element::BigInt(big)]),
+                &vector(&[2])
+            )
+            .unwrap(),
+            vector(&[i64::MAX])
+        );
+
+        let half = Element::Rational(BigRational::new(1.into(), 2.into()));
+        let third = Element::Rational(BigRational::new(1.into(), 3.into()));
+        assert_eq!(
+            add(&half, &third).unwrap(),
+            Element::Rational(BigRational::new(5.into(), 6.into()))
+        );
+        assert_eq!(add(&half, &half).unwrap(), Element::Int(1));
+        assert_eq!(
+            multiply(&half, &Element::Float(3.0)).unwrap(),
+            Element::Float(1.5)
+        );
+        assert_eq!(floor(&half).unwrap(), Element::Int(0));
+        assert_eq!(less(&third, &half).unwrap(), Element::Int(1));
+        assert_eq!(
+            residue(&third, &half).unwrap(),
+            rational(BigRational::new(1.into(), 6.into()))
+        );
+    }
+
+    #[test]
+    fn scalar7() {
+        let matrix = Array::from_parts(vec![2, 2], vec![Element::Int(1); 4]);
+        assert_eq!(
+            dyad(add, &vector(&[10, 20]), &matrix).unwrap(),
+            Array::from_parts(
+                vec![2, 2],
+                [11, 11, 21, 21].iter().copied().map(Element::Int).collect()
+            )
+        );
+        assert!(dyad(add, &vector(&[1, 2, 3]), &matrix).is_err());
+    }
+
+    #[test]
+    fn scalar8() {
+        assert_eq!(
+            dyad(power, &vector(&[2]), &vector(&[0, 3, 70])).unwrap(),
+            Array::vector(vec![
+                Element::Int(1),
+                Element::Int(8),
+                integer(BigInt::from(1) << 70),
+            ])
+        );
+        assert_eq!(
+            power(&Element::Int(2), &Element::Int(-2)).unwrap(),
+            Element::Float(0.25)
+        );
+        let half = rational(BigRational::new(1.into(), 2.into()));
+        assert_eq!(power(&half, &Element::Int(-3)).unwrap(), Element::Int(8));
+        assert_eq!(
+            power(&Element::Int(4), &Element::Float(0.5)).unwrap(),
+            Element::Float(2.0)
+        );
+        match power(&Element::Int(-4), &Element::Float(0.5)).unwrap() {
+            Element::Complex(c) => assert!(c.re.abs() < 1e-12 && (c.im - 2.0).abs() < 1e-12),
+            element => panic!("expected a complex number, got {:?}", element),
+        }
+        assert!(power(&Element::Int(0), &Element::Int(-1)).is_err());
+
+        // powers too big to be exact quickly are floats
+        assert_eq!(
+            power(&Element::Int(2), &Element::Int(2_000_000_000)).unwrap(),
+            Element::Float(f64::INFINITY)
+        );
+        assert_eq!(
+            power(&half, &Element::Int(i64::MAX)).unwrap(),
+            Element::Float(0.0)
+        );
+        assert_eq!(
+            power(&Element::Int(-1), &Element::Int(2_000_000_001)).unwrap(),
+            Element::Int(-1)
+        );
+        assert_eq!(exponential(&Element::Int(0)).unwrap(), Element::Float(1.0));
+    }
+
+    #[test]
+    fn scalar9() {
+        let float = Element::Float;
+        assert!(same(&float(0.1 + 0.2), &float(0.3)));
+        assert!(same(&float(-0.0), &float(0.0)));
+        assert!(!same(&float(1.0), &float(1.0 + 1e-12)));
+        assert!(!same(&float(1e-300), &float(0.0)));
+        assert!(!same(&float(f64::INFINITY), &float(f64::MAX)));
+        assert!(same(&Element::Int(2), &float(2.0)));
+        assert!(!same(&Element::Int(1), &float(1.0 + 1e-12)));
+
+        // exact numbers are rounded to floats to compare with them, so 49×÷49 is the same as 1
+        let one = multiply(&Element::Int(49), &reciprocal(&Element::Int(49)).unwrap()).unwrap();
+        assert_eq!(one, float(1.0 - f64::EPSILON / 2.0));
+        assert!(same(&Element::Int(1), &one));
+        assert!(same(&float(1.0), &one));
+        let half = Element::Rational(BigRational::new(1.into(), 2.into()));
+        assert!(same(&half, &float(0.5 + 1e-17)));
+
+        // 2^53 + 1 rounds to the float 2^53, so both are the same as it, but exact numbers are compared exactly
+        let big = 1i64 << 53;
+        assert!(same(&Element::Int(big), &float(big as f64)));
+        assert!(same(&Element::Int(big + 1), &float(big as f64)));
+        assert!(!same(&Element::Int(big + 1), &Element::Int(big)));
+
+        assert_eq!(
+            equal(&float(0.1 + 0.2), &float(0.3)).unwrap(),
+            Element::Int(1)
+        );
+        assert_eq!(
+            not_equal(&float(0.1 + 0.2), &float(0.3)).unwrap(),
+            Element::Int(0)
+        );
+        assert_eq!(equal(&Element::Int(1), &one).unwrap(), Element::Int(1));
+    }
+
+    #[test]
+    fn scalar10() {
+        let float = Element::Float;
+        let (sum, tenths) = (float(0.1 + 0.2), float(0.3));
+        assert_eq!(less_equal(&sum, &tenths).unwrap(), Element::Int(1));
+        assert_eq!(greater_equal(&tenths, &sum).unwrap(), Element::Int(1));
+        assert_eq!(less(&tenths, &sum).unwrap(), Element::Int(0));
+        assert_eq!(greater(&sum, &tenths).unwrap(), Element::Int(0));
+        assert_eq!(maximum(&tenths, &sum).unwrap(), tenths);
+        assert_eq!(minimum(&tenths, &sum).unwrap(), tenths);
+        assert_eq!(
+            less(&float(0.3), &float(0.3 + 1e-12)).unwrap(),
+            Element::Int(1)
+        );
+
+        let one = float(1.0 - f64::EPSILON / 2.0);
+        assert_eq!(less(&one, &Element::Int(1)).unwrap(), Element::Int(0));
+        assert_eq!(
+            greater_equal(&one, &Element::Int(1)).unwrap(),
+            Element::Int(1)
+        );
+        assert_eq!(floor(&one).unwrap(), Element::Int(1));
+        assert_eq!(
+            ceiling(&float(1.0 + f64::EPSILON)).unwrap(),
+            Element::Int(1)
+        );
+        assert_eq!(floor(&float(2.5)).unwrap(), Element::Int(2));
+        assert_eq!(ceiling(&float(-2.5)).unwrap(), Element::Int(-2));
+        assert!(less(&float(f64::NAN), &float(1.0)).is_err());
+    }
+}
@@ -4,6 +4,8 @@ use crate::prelude::{anyhow, ErrorKind, Result};
 
 use unicode_segmentation::{Graphemes, UnicodeSegmentation};
 
+use std::num::IntErrorKind;
+
 /// Kinds of tokens
 ///
 /// Tokens that must require symmetry have both forms as token types, the rest fall under Builtin or literal token types.
@@ -19,42 +21,131 @@ pub enum TokenKind {
     // any punctuation or APL character
     Builtin,
     Int(i64),
+    // an integer too big for Int, whose value is in the lexeme
+    BigInt,
+    // a ratio like 3r4, whose value is in the lexeme
+    Rational,
     Float(f64),
     Complex(f64, f64),
     String,
+    // ∆ followed by an identifier
+    Atom,
+    // ⍝ through the end of the line, only emitted if the scanner is asked to keep comments
+    Comment,
     EOF,
 }
 
+/// Identifies the source a span came from
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, Default)]
+pub struct FileId(usize);
+
+impl FileId {
+    /// Create a file identifier.
+    pub fn new(index: usize) -> FileId {
+        FileId(index)
+    }
+
+    /// Get the index of the file.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// A position in a language source
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+pub struct Location {
+    line: usize,
+    column: usize,
+    byte: usize,
+}
+
+impl Location {
+    /// Create a new location.
+    pub(crate) fn new(line: usize, column: usize, byte: usize) -> Location {
+        Location { line, column, byte }
+    }
+
+    /// Get the line of the location, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the grapheme index of the location in its line, starting from 0.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get the byte offset of the location in the source.
+    pub fn byte(&self) -> usize {
+        self.byte
+    }
+}
+
 /// Indices into a language source
-#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Spans cover everything from their start up to but not including their end.
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
 pub struct Span {
-    line: usize,
-    grapheme_index_in_line: usize,
+    file: FileId,
+    start: Location,
+    end: Location,
 }
 
 impl Span {
     /// Create a new span.
-    pub(crate) fn new(line: usize, grapheme_index_in_line: usize) -> Span {
-        Span {
-            line,
-            grapheme_index_in_line,
-        }
+    pub(crate) fn new(file: FileId, start: Location, end: Location) -> Span {
+        Span { file, start, end }
+    }
+
+    /// Get the file the span is in.
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    /// Get the start of the span.
+    pub fn start(&self) -> Location {
+        self.start
+    }
+
+    /// Get the end of the span.
+    pub fn end(&self) -> Location {
+        self.end
     }
 
-    /// Get the line of the span.
+    /// Get the line the span starts on.
     pub fn line(&self) -> usize {
-        self.line
+        self.start.line
+    }
+
+    /// Get the byte range of the span in its source.
+    pub fn bytes(&self) -> std::ops::Range<usize> {
+        self.start.byte..self.end.byte
     }
 
-    /// Get grapheme index in the line of the span.
-    pub fn grapheme_index_in_line(&self) -> usize {
-        self.grapheme_index_in_line
+    /// Create a span covering both this span and another.
+    pub fn merge(self, other: Span) -> Span {
+        let start = if other.start.byte < self.start.byte {
+            other.start
+        } else {
+            self.start
+        };
+        let end = if other.end.byte > self.end.byte {
+            other.end
+        } else {
+            self.end
+        };
+
+        Span {
+            file: self.file,
+            start,
+            end,
+        }
     }
 }
 
 impl std::fmt::Display for Span {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "line {} char {}", self.line, self.grapheme_index_in_line)
+        write!(f, "line {} char {}", self.start.line, self.start.column + 1)
     }
 }
 
@@ -81,10 +172,7 @@ impl Token {
         Token {
             kind,
             lexeme: lexeme.to_string(),
-            span: Span {
-                line: 0,
-                grapheme_index_in_line: 0,
-            },
+            span: Span::default(),
         }
     }
 
@@ -117,43 +205,68 @@ const YES_EXTENDED_GRAPHEMES: bool = true;
 pub struct Scanner<'a> {
     graphemes: std::iter::Peekable<Graphemes<'a>>,
     source: &'a str,
+    file: FileId,
     line: usize,
     grapheme_index_in_line: usize,
+    byte: usize,
+    // where the token currently being scanned starts
+    start: Location,
+    keep_comments: bool,
 }
 
 impl<'a> Scanner<'a> {
     /// Create a new scanner from a source.
     pub fn new(source: &'a str) -> Scanner<'a> {
+        Scanner::with_file(source, FileId::default())
+    }
+
+    /// Create a new scanner from a source, with spans pointing into the given file.
+    pub fn with_file(source: &'a str, file: FileId) -> Scanner<'a> {
         Scanner {
             graphemes: source.graphemes(YES_EXTENDED_GRAPHEMES).peekable(),
             source,
+            file,
             line: 1,
             grapheme_index_in_line: 0,
+            byte: 0,
+            start: Location::new(1, 0, 0),
+            keep_comments: false,
         }
     }
 
+    /// Set whether comments are emitted as tokens instead of being skipped.
+    pub fn keep_comments(mut self, keep_comments: bool) -> Scanner<'a> {
+        self.keep_comments = keep_comments;
+        self
+    }
+
     // Advance to the next line, only called in next_grapheme.
     fn newline(&mut self) {
         self.line += 1;
         self.grapheme_index_in_line = 0;
     }
 
-    // Get the current span of the scanner.
+    // Get the current location of the scanner.
+    fn location(&self) -> Location {
+        Location::new(self.line, self.grapheme_index_in_line, self.byte)
+    }
+
+    // Get the span from the start of the current token to the current location.
     fn span(&self) -> Span {
-        Span::new(self.line, self.grapheme_index_in_line)
+        Span::new(self.file, self.start, self.location())
     }
 
     // Get the next grapheme from the grapheme iterator.
     fn next_grapheme(&mut self) -> Option<&'a str> {
-        self.graphemes.next().map(|grapheme| {
+        self.graphemes.next().inspect(|&grapheme| {
+            self.byte += grapheme.len();
+
             // increment the line number if necessary
             if grapheme == "\n" {
                 self.newline();
             } else {
                 self.grapheme_index_in_line += 1;
             }
-
-            grapheme
         })
     }
 
@@ -164,12 +277,27 @@ impl<'a> Scanner<'a> {
 
     /// Get the next token from the source.
     pub fn next_token(&mut self) -> Result<Token> {
-        // get the next grapheme
-        let mut grapheme = self.next_grapheme().unwrap_or("");
-        while util::is_whitespace(grapheme) {
-            // skip past whitespace
-            grapheme = self.next_grapheme().unwrap_or("");
+        loop {
+            let token = self.scan_token()?;
+            if token.kind != TokenKind::Comment || self.keep_comments {
+                return Ok(token);
+            }
         }
+    }
+
+    // Scan the next token, including comments.
+    fn scan_token(&mut self) -> Result<Token> {
+        // skip past whitespace
+        while let Some(grapheme) = self.peek_grapheme() {
+            if !util::is_whitespace(grapheme) {
+                break;
+            }
+            self.next_grapheme().unwrap();
+        }
+
+        // get the next grapheme, starting a new token
+        self.start = self.location();
+        let grapheme = self.next_grapheme().unwrap_or("");
 
         match grapheme {
             "" => Ok(Token::new(TokenKind::EOF, "", self.span())),
@@ -181,6 +309,9 @@ impl<'a> Scanner<'a> {
             g @ "]" => Ok(Token::new(TokenKind::RightBracket, g, self.span())),
             g @ "¯" => self.number(g), // negative number literals use an over-score
             g @ "'" => self.string(g),
+            g @ "⍝" => Ok(self.comment(g)),
+            g @ "∆" => Ok(self.atom(g)),
+            g @ "⎕" => Ok(self.system(g)),
             g if util::is_builtin(g) => Ok(Token::new(TokenKind::Builtin, g, self.span())),
             g if util::is_digit(g) => self.number(g),
             g if util::is_whitespace(g) => unreachable!(),
@@ -204,6 +335,7 @@ impl<'a> Scanner<'a> {
                     if quote != "'" {
                         return Err(anyhow!(ErrorKind::UnknownEscapeCode {
                             code: quote.to_string(),
+                            span: self.span(),
                         }));
                     }
                 }
@@ -228,6 +360,21 @@ impl<'a> Scanner<'a> {
         Err(anyhow!(ErrorKind::UnterminatedString { span: self.span() }))
     }
 
+    // Scan a comment, up to but not including the end of the line.
+    fn comment(&mut self, grapheme: &str) -> Token {
+        let mut comment = String::from(grapheme);
+
+        while let Some(grapheme) = self.peek_grapheme() {
+            if grapheme == "\n" || grapheme == "\r\n" {
+                break;
+            }
+            comment.push_str(grapheme);
+            self.next_grapheme().unwrap();
+        }
+
+        Token::new(TokenKind::Comment, comment, self.span())
+    }
+
     // Scan an identifier.
     fn ident(&mut self, grapheme: &str) -> Result<Token> {
         // start the identifier
@@ -248,42 +395,91 @@ impl<'a> Scanner<'a> {
         Ok(Token::new(TokenKind::Ident, ident, self.span()))
     }
 
+    // Scan an atom, or just ∆ if there's no name after it.
+    fn atom(&mut self, grapheme: &str) -> Token {
+        self.prefixed(grapheme, TokenKind::Atom)
+    }
+
+    // Scan the name of a system variable like ⎕IO, which is an identifier, or just ⎕ if there's no name after it.
+    fn system(&mut self, grapheme: &str) -> Token {
+        self.prefixed(grapheme, TokenKind::Ident)
+    }
+
+    // Scan a glyph with a name directly after it as one token, or just the glyph.
+    fn prefixed(&mut self, grapheme: &str, kind: TokenKind) -> Token {
+        match self.peek_grapheme() {
+            Some(next) if util::is_identifier(next) && !util::is_digit(next) => {
+                let mut name = String::from(grapheme);
+                while let Some(grapheme) = self.peek_grapheme() {
+                    if !util::is_identifier(grapheme) {
+                        break;
+                    }
+                    name.push_str(grapheme);
+                    self.next_grapheme().unwrap();
+                }
+                Token::new(kind, name, self.span())
+            }
+            _ => Token::new(TokenKind::Builtin, grapheme, self.span()),
+        }
+    }
+
     // Scan a number.
     fn number(&mut self, grapheme: &str) -> Result<Token> {
-        // start the identifier
+        // start the number
         let mut number = String::from(grapheme);
+        self.mantissa(&mut number)?;
+
+        // jump out if it isn't a simple integer
+        match self.peek_grapheme() {
+            // if we find a . we're scanning a float, continue doing that
+            Some(".") => return self.float(number),
+            // if we find a j it's a complex number
+            Some("J") | Some("j") => return self.complex(number),
+            // e is scientific notation
+            Some("E") | Some("e") => return self.scientific(number),
+            // r makes a rational
+            Some("r") => return self.rational(number),
+            _ => {}
+        }
 
-        // while we still have graphemes
-        while let Some(grapheme) = self.peek_grapheme() {
-            // if it's a digit, add it to the number
-            if util::is_digit(grapheme) {
-                number.push_str(grapheme);
-            } else if grapheme == "." {
-                // if we find a . we're scanning a float, continue doing that
-                return self.float(number);
-            } else if grapheme == "J" || grapheme == "j" {
-                // if we find a j it's a complex number
-                return self.complex(number);
-            } else if grapheme == "E" || grapheme == "e" {
-                // e is scientific notation
-                return self.scientific(number);
-            } else {
-                break;
+        match number.replace("¯", "-").parse::<i64>() {
+            Ok(int) => Ok(Token::new(TokenKind::Int(int), number, self.span())),
+            // integers that don't fit are still integers
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+                ) =>
+            {
+                Ok(Token::new(TokenKind::BigInt, number, self.span()))
             }
-            self.next_grapheme().unwrap();
+            Err(e) => Err(ErrorKind::SyntaxError {
+                why: anyhow!(e),
+                span: self.span(),
+            }
+            .into()),
         }
+    }
 
-        Ok(Token::new(
-            TokenKind::Int(number.replace("¯", "-").parse::<i64>().map_err(|e| {
-                ErrorKind::SyntaxError {
-                    // TODO there's probably a better way of doing this
-                    why: anyhow!(e),
-                    span: self.span(),
-                }
-            })?),
-            number,
-            self.span(),
-        ))
+    // Scan a rational number, like 3r4.
+    fn rational(&mut self, mut number: String) -> Result<Token> {
+        // sanity
+        assert_eq!("r", self.next_grapheme().unwrap());
+        number.push('r');
+
+        if self.peek_grapheme() == Some("¯") {
+            number.push_str(self.next_grapheme().unwrap());
+        }
+
+        let start = number.len();
+        if self.digits(&mut number) == 0 {
+            return Err(self.malformed(format!("expected a denominator after {}", number)));
+        }
+        if number[start..].chars().all(|c| c == '0') {
+            return Err(self.malformed(format!("{} has a zero denominator", number)));
+        }
+
+        Ok(Token::new(TokenKind::Rational, number, self.span()))
     }
 
     // Scan a floating-point number.
@@ -291,51 +487,135 @@ impl<'a> Scanner<'a> {
         // sanity
         assert_eq!(".", self.next_grapheme().unwrap());
         number.push('.');
+        self.digits(&mut number);
 
-        // get the rest of the number, jumping out if it's not a simple float
-        while let Some(grapheme) = self.peek_grapheme() {
-            if util::is_digit(grapheme) {
-                number.push_str(grapheme);
-            } else if grapheme == "J" || grapheme == "j" {
-                return self.complex_float(number);
-            } else if grapheme == "E" || grapheme == "e" {
-                return self.scientific_float(number);
-            } else {
-                break;
-            }
-            self.next_grapheme().unwrap();
+        // jump out if it's not a simple float
+        match self.peek_grapheme() {
+            Some("J") | Some("j") => return self.complex_float(number),
+            Some("E") | Some("e") => return self.scientific_float(number),
+            _ => {}
         }
 
+        let float = self.parse_float(&number)?;
+        Ok(Token::new(TokenKind::Float(float), number, self.span()))
+    }
+
+    // Scan a complex number with an integer real part, like 3j4.
+    fn complex(&mut self, number: String) -> Result<Token> {
+        self.complex_float(number)
+    }
+
+    // Scan an integer with an exponent, like 1e5. These are floats, as in J.
+    fn scientific(&mut self, number: String) -> Result<Token> {
+        self.scientific_float(number)
+    }
+
+    // Scan a complex number with a real part that may have a fractional part and exponent.
+    fn complex_float(&mut self, mut number: String) -> Result<Token> {
+        let real = self.parse_float(&number)?;
+
+        let j = self.next_grapheme().unwrap();
+        assert!(j == "j" || j == "J");
+        number.push_str(j);
+
+        // the imaginary part is a whole real number of its own
+        let start = number.len();
+        let negative = self.peek_grapheme() == Some("¯");
+        if negative {
+            number.push_str(self.next_grapheme().unwrap());
+        }
+        if self.digits(&mut number) == 0 {
+            return Err(self.malformed(format!("expected the imaginary part of {}", number)));
+        }
+        if self.peek_grapheme() == Some(".") {
+            number.push_str(self.next_grapheme().unwrap());
+            self.digits(&mut number);
+        }
+        if matches!(self.peek_grapheme(), Some("E") | Some("e")) {
+            self.exponent(&mut number)?;
+        }
+
+        let imaginary = self.parse_float(&number[start..])?;
         Ok(Token::new(
-            TokenKind::Float(number.replace("¯", "-").parse::<f64>().map_err(|e| {
-                ErrorKind::SyntaxError {
-                    why: anyhow!(e),
-                    span: self.span(),
-                }
-            })?),
+            TokenKind::Complex(real, imaginary),
             number,
             self.span(),
         ))
     }
 
-    fn complex(&mut self, _number: String) -> Result<Token> {
-        todo!("complex integer")
+    // Scan a float with an exponent, like 1.5e¯3.
+    fn scientific_float(&mut self, mut number: String) -> Result<Token> {
+        self.exponent(&mut number)?;
+
+        if matches!(self.peek_grapheme(), Some("J") | Some("j")) {
+            return self.complex_scientific_float(number);
+        }
+
+        let float = self.parse_float(&number)?;
+        Ok(Token::new(TokenKind::Float(float), number, self.span()))
+    }
+
+    // Scan a complex number with a real part in scientific notation, like 1e3j4.
+    fn complex_scientific_float(&mut self, number: String) -> Result<Token> {
+        self.complex_float(number)
     }
 
-    fn scientific(&mut self, _number: String) -> Result<Token> {
-        todo!("scientific integer")
+    // Scan the digits of a number after an optional ¯, which must have at least one digit.
+    fn mantissa(&mut self, number: &mut String) -> Result<()> {
+        if self.digits(number) == 0 && !number.chars().any(|c| c.is_ascii_digit()) {
+            return Err(self.malformed(format!("expected digits after {}", number)));
+        }
+        Ok(())
+    }
+
+    // Scan an exponent, including the e.
+    fn exponent(&mut self, number: &mut String) -> Result<()> {
+        let e = self.next_grapheme().unwrap();
+        assert!(e == "e" || e == "E");
+        number.push_str(e);
+
+        if self.peek_grapheme() == Some("¯") {
+            number.push_str(self.next_grapheme().unwrap());
+        }
+
+        if self.digits(number) == 0 {
+            return Err(self.malformed(format!("expected an exponent after {}", number)));
+        }
+
+        Ok(())
     }
 
-    fn complex_float(&mut self, _number: String) -> Result<Token> {
-        todo!("complex float")
+    // Scan as many digits as there are, returning how many there were.
+    fn digits(&mut self, number: &mut String) -> usize {
+        let mut count = 0;
+        while let Some(grapheme) = self.peek_grapheme() {
+            if !util::is_digit(grapheme) {
+                break;
+            }
+            number.push_str(grapheme);
+            self.next_grapheme().unwrap();
+            count += 1;
+        }
+        count
     }
 
-    fn scientific_float(&mut self, _number: String) -> Result<Token> {
-        todo!("scientific float")
+    // Parse a real number, which may use ¯ for negatives.
+    fn parse_float(&self, number: &str) -> Result<f64> {
+        Ok(number
+            .replace("¯", "-")
+            .parse::<f64>()
+            .map_err(|e| ErrorKind::SyntaxError {
+                why: anyhow!(e),
+                span: self.span(),
+            })?)
     }
 
-    fn complex_scientific_float(&mut self, _number: String) -> Result<Token> {
-        todo!("complex scientific float")
+    // Create an error for a malformed number literal.
+    fn malformed(&self, why: String) -> anyhow::Error {
+        anyhow!(ErrorKind::SyntaxError {
+            why: anyhow!(why),
+            span: self.span(),
+        })
     }
 }
 
@@ -451,7 +731,7 @@ mod util {
 
     pub(crate) fn is_whitespace(s: &str) -> bool {
         // TODO: other weird forms of whitespace
-        s.len() == 1 && s.bytes().nth(0).unwrap().is_ascii_whitespace()
+        s.len() == 1 && s.as_bytes()[0].is_ascii_whitespace()
     }
 
     pub(crate) fn is_digit(s: &str) -> bool {
@@ -465,6 +745,7 @@ mod util {
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod test {
     use super::*;
 
@@ -909,12 +1190,176 @@ mod test {
         println!("{:?}", s.next_token().unwrap());
     }
 
-    #[should_panic]
     #[test]
     fn scan19() {
         let mut s = Scanner::new(
             "578419057648432954637895647381946573821964378912467389216473812964739821",
         );
-        println!("{:?}", s.next_token().unwrap());
+        assert!(s.next_token().unwrap().compare_no_span(Token::no_span(
+            TokenKind::BigInt,
+            "578419057648432954637895647381946573821964378912467389216473812964739821"
+        )));
+    }
+
+    #[test]
+    fn scan20() {
+        let mut s = Scanner::new("x ← 'a\nb' ⍴⍴ 32");
+        let x = s.next_token().unwrap().span();
+        assert_eq!((x.start().column(), x.end().column()), (0, 1));
+        let arrow = s.next_token().unwrap().span();
+        assert_eq!((arrow.start().column(), arrow.end().column()), (2, 3));
+        assert_eq!(arrow.bytes(), 2..5);
+
+        // strings can span lines
+        let string = s.next_token().unwrap().span();
+        assert_eq!((string.start().line(), string.start().column()), (1, 4));
+        assert_eq!((string.end().line(), string.end().column()), (2, 2));
+        assert_eq!(string.bytes(), 6..11);
+
+        s.next_token().unwrap();
+        let rho = s.next_token().unwrap().span();
+        let number = s.next_token().unwrap().span();
+        assert_eq!((number.start().column(), number.end().column()), (6, 8));
+        assert_eq!(rho.merge(number).bytes(), 15..21);
+        assert_eq!(number.merge(rho), rho.merge(number));
+    }
+
+    #[test]
+    fn scan21() {
+        let mut s = Scanner::with_file("1", FileId::new(3));
+        assert_eq!(s.next_token().unwrap().span().file(), FileId::new(3));
+        assert_eq!(FileId::new(3).index(), 3);
+    }
+
+    #[test]
+    fn scan22() {
+        let v: Vec<_> = Scanner::new("x ← 1 ⍝ pick {\n⍝ append\nx,x ⍝")
+            .map(|t| t.lexeme)
+            .collect();
+        assert_eq!(v, vec!["x", "←", "1", "x", ",", "x"]);
+
+        let mut s = Scanner::new("1 ⍝ one\n2").keep_comments(true);
+        assert!(s
+            .next_token()
+            .unwrap()
+            .compare_no_span(Token::no_span(TokenKind::Int(1), "1")));
+        let comment = s.next_token().unwrap();
+        assert!(comment.compare_no_span(Token::no_span(TokenKind::Comment, "⍝ one")));
+        assert_eq!(comment.span().end().column(), 7);
+        assert!(s
+            .next_token()
+            .unwrap()
+            .compare_no_span(Token::no_span(TokenKind::Int(2), "2")));
+    }
+
+    #[test]
+    fn scan23() {
+        let v: Vec<_> = Scanner::new("'⍝ not a comment' ⍝ but this is")
+            .map(|t| t.lexeme)
+            .collect();
+        assert_eq!(v, vec!["'⍝ not a comment'"]);
+    }
+
+    #[test]
+    fn scan24() {
+        let v: Vec<_> = Scanner::new("1e5 1E¯2 ¯2.5e3 3j4 ¯1.5J¯2 1e2j1e1 0j1")
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(
+            v,
+            vec![
+                TokenKind::Float(1e5),
+                TokenKind::Float(1e-2),
+                TokenKind::Float(-2.5e3),
+                TokenKind::Complex(3.0, 4.0),
+                TokenKind::Complex(-1.5, -2.0),
+                TokenKind::Complex(1e2, 1e1),
+                TokenKind::Complex(0.0, 1.0),
+            ]
+        );
+
+        let mut s = Scanner::new("2.5e¯3 x");
+        assert!(s
+            .next_token()
+            .unwrap()
+            .compare_no_span(Token::no_span(TokenKind::Float(2.5e-3), "2.5e¯3")));
+        assert!(s
+            .next_token()
+            .unwrap()
+            .compare_no_span(Token::no_span(TokenKind::Ident, "x")));
+    }
+
+    #[test]
+    fn scan25() {
+        for source in ["1e", "1e¯", "3j", "3j¯", "1.5e+3", "¯", "¯x", "2je"] {
+            let error = Scanner::new(source).next_token().err().unwrap();
+            assert!(
+                matches!(
+                    error.downcast_ref::<ErrorKind>(),
+                    Some(ErrorKind::SyntaxError { .. })
+                ),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn scan26() {
+        let v: Vec<_> = Scanner::new("3r4 ¯1r¯2 6r3 ¯9223372036854775809")
+            .map(|t| (t.kind, t.lexeme))
+            .collect();
+        assert_eq!(
+            v,
+            vec![
+                (TokenKind::Rational, String::from("3r4")),
+                (TokenKind::Rational, String::from("¯1r¯2")),
+                (TokenKind::Rational, String::from("6r3")),
+                (TokenKind::BigInt, String::from("¯9223372036854775809")),
+            ]
+        );
+
+        for source in ["3r", "3r0", "1r¯"] {
+            assert!(Scanner::new(source).next_token().is_err(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn scan27() {
+        let v: Vec<_> = Scanner::new("∆person ⎕ ∆name∆year ∆ 1 ∆2")
+            .map(|t| (t.kind, t.lexeme))
+            .collect();
+        assert_eq!(
+            v,
+            vec![
+                (TokenKind::Atom, String::from("∆person")),
+                (TokenKind::Builtin, String::from("⎕")),
+                (TokenKind::Atom, String::from("∆name")),
+                (TokenKind::Atom, String::from("∆year")),
+                (TokenKind::Builtin, String::from("∆")),
+                (TokenKind::Int(1), String::from("1")),
+                (TokenKind::Builtin, String::from("∆")),
+                (TokenKind::Int(2), String::from("2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan28() {
+        let v: Vec<_> = Scanner::new("⎕IO←0 ⋄ ⎕ ∆int ⎕io")
+            .map(|t| (t.kind, t.lexeme))
+            .collect();
+        assert_eq!(
+            v,
+            vec![
+                (TokenKind::Ident, String::from("⎕IO")),
+                (TokenKind::Builtin, String::from("←")),
+                (TokenKind::Int(0), String::from("0")),
+                (TokenKind::Builtin, String::from("⋄")),
+                (TokenKind::Builtin, String::from("⎕")),
+                (TokenKind::Atom, String::from("∆int")),
+                (TokenKind::Ident, String::from("⎕io")),
+            ]
+        );
     }
 }